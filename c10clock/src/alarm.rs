@@ -0,0 +1,286 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Alarms set for C10 times of day.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::timer::FLASH_TICKS;
+
+/// A time of day to go off at, every day or on one day of the year.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Alarm {
+    // the decaday and day, if only going off on that date
+    date: Option<(u64, u64)>,
+    // since midnight
+    time: c10::Duration,
+}
+
+impl Alarm {
+    /// Returns the first time strictly after `after` that the alarm goes off, read on the same
+    /// clock as `after`, or `None` if that cannot be represented.
+    pub fn next_after(&self, after: c10::SystemTime) -> Option<c10::SystemTime> {
+        let (ints, cents, ticks) = self.time.time_components();
+        match self.date {
+            None => {
+                let days = after.as_ticks().div_euclid(c10::DAY.as_ticks() as i64);
+                let midnight = c10::SystemTime::from_ticks(days * c10::DAY.as_ticks() as i64);
+                let alarm = midnight.ok()?.checked_add(self.time)?;
+                match alarm > after {
+                    true => Some(alarm),
+                    false => alarm.checked_add(c10::DAY),
+                }
+            }
+            Some((decaday, day)) => {
+                // the sixth epagomenal day only comes in leap years, at most eight years apart
+                let (year, _, _) = after.date_components();
+                (year..=year + 8).find_map(|year| {
+                    c10::SystemTime::from_c10_components(year, decaday, day, ints, cents, ticks)
+                        .ok()
+                        .filter(|&alarm| alarm > after)
+                })
+            }
+        }
+    }
+}
+
+/// Parses `[DD.dd ]II:CC:TT`, a time of day optionally preceded by a decaday and day.
+impl FromStr for Alarm {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (date, time) = match text.trim().split_once(char::is_whitespace) {
+            Some((date, time)) => (Some(date), time.trim_start()),
+            None => (None, text.trim()),
+        };
+
+        let time: c10::Duration = time.parse().map_err(|err| format!("{err}"))?;
+        if time >= c10::DAY {
+            return Err(format!("{time} is not a time of day"));
+        }
+
+        let date = match date {
+            None => None,
+            Some(date) => {
+                let invalid = || format!("invalid date {date:?}, expected decaday.day");
+                let (decaday, day) = date.split_once('.').ok_or_else(invalid)?;
+                let decaday: u64 = decaday.parse().map_err(|_| invalid())?;
+                let day: u64 = day.parse().map_err(|_| invalid())?;
+                let days = if decaday == 37 { 6 } else { 10 };
+                if !(1..=37).contains(&decaday) || !(1..=days).contains(&day) {
+                    return Err(format!("there is no day {decaday}.{day:02}"));
+                }
+                Some((decaday, day))
+            }
+        };
+        Ok(Alarm { date, time })
+    }
+}
+
+impl fmt::Display for Alarm {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((decaday, day)) = self.date {
+            write!(fmter, "{decaday}.{day:02} ")?;
+        }
+        write!(fmter, "{}", self.time)
+    }
+}
+
+/// Alarms waiting to go off, and the one ringing, if any.
+///
+/// Every check compares the time given against when each alarm is next due, so an alarm missed
+/// while the machine was suspended goes off as soon as it resumes.
+#[derive(Debug, Clone)]
+pub struct Alarms {
+    alarms: Vec<Alarm>,
+    // when each alarm next goes off
+    next: Vec<Option<c10::SystemTime>>,
+    // the alarm ringing and when it started
+    ringing: Option<(Alarm, c10::SystemTime)>,
+}
+
+impl Alarms {
+    /// Returns `alarms` set to go off after `now`.
+    pub fn new(alarms: Vec<Alarm>, now: c10::SystemTime) -> Self {
+        let next = alarms.iter().map(|alarm| alarm.next_after(now)).collect();
+        Alarms {
+            alarms,
+            next,
+            ringing: None,
+        }
+    }
+
+    /// Returns whether any alarm went off by `now` since the last check, setting it to ring and
+    /// each alarm that went off to go off again at its next time after `now`.
+    pub fn check(&mut self, now: c10::SystemTime) -> bool {
+        let mut rang = false;
+        for (alarm, next) in self.alarms.iter().zip(&mut self.next) {
+            if next.is_some_and(|next| next <= now) {
+                *next = alarm.next_after(now);
+                self.ringing = Some((*alarm, now));
+                rang = true;
+            }
+        }
+        rang
+    }
+
+    /// Stops the ringing.
+    pub fn dismiss(&mut self) {
+        self.ringing = None;
+    }
+
+    /// Returns whether the display should be inverted at `now`, which it is every other few
+    /// ticks while an alarm rings.
+    pub fn flash(&self, now: c10::SystemTime) -> bool {
+        self.ringing.is_some_and(|(_, since)| {
+            let ringing = now.duration_since(since).unwrap_or(c10::Duration::ZERO);
+            (ringing.as_ticks() / FLASH_TICKS).is_multiple_of(2)
+        })
+    }
+
+    /// Returns lines describing the alarms, to show under the clock.
+    pub fn status(&self) -> Vec<String> {
+        let mut lines = vec![match self.ringing {
+            Some((alarm, _)) => format!("ALARM {alarm}   space dismiss   q quit"),
+            None => "waiting   q quit".to_owned(),
+        }];
+        lines.push(String::new());
+        for (alarm, next) in self.alarms.iter().zip(&self.next) {
+            lines.push(match next {
+                Some(next) => format!("{alarm:>14}   next {next}"),
+                None => format!("{alarm:>14}   never"),
+            });
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(
+        year: i64,
+        decaday: u64,
+        day: u64,
+        ints: u64,
+        cents: u64,
+        ticks: u64,
+    ) -> c10::SystemTime {
+        c10::SystemTime::from_c10_components(year, decaday, day, ints, cents, ticks).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let alarm: Alarm = "75:00:00".parse().unwrap();
+        assert_eq!(alarm.date, None);
+        assert_eq!(alarm.time, 75 * c10::INTERVAL);
+        assert_eq!(alarm.to_string(), "75:00:00");
+
+        let alarm: Alarm = "12.03 5:0:1".parse().unwrap();
+        assert_eq!(alarm.date, Some((12, 3)));
+        assert_eq!(alarm.time, 5 * c10::INTERVAL + c10::TICK);
+        assert_eq!(alarm.to_string(), "12.03 05:00:01");
+        assert!("37.06 00:00:00".parse::<Alarm>().is_ok());
+
+        for invalid in [
+            "",
+            "soon",
+            "100:00:00",
+            "12 75:00:00",
+            "0.01 75:00:00",
+            "37.07 1:0:0",
+        ] {
+            assert!(invalid.parse::<Alarm>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn daily() {
+        let alarm: Alarm = "75:00:00".parse().unwrap();
+        assert_eq!(
+            alarm.next_after(time(2023, 1, 1, 10, 0, 0)),
+            Some(time(2023, 1, 1, 75, 0, 0))
+        );
+        // strictly after, so an alarm does not go off twice at the same time
+        assert_eq!(
+            alarm.next_after(time(2023, 1, 1, 75, 0, 0)),
+            Some(time(2023, 1, 2, 75, 0, 0))
+        );
+        assert_eq!(
+            alarm.next_after(time(2023, 37, 5, 80, 0, 0)),
+            Some(time(2024, 1, 1, 75, 0, 0))
+        );
+
+        // before the epoch too
+        let before = c10::SystemTime::UNIX_EPOCH - 30 * c10::INTERVAL;
+        assert_eq!(alarm.next_after(before), Some(time(1969, 37, 5, 75, 0, 0)));
+    }
+
+    #[test]
+    fn dated() {
+        let alarm: Alarm = "12.03 75:00:00".parse().unwrap();
+        assert_eq!(
+            alarm.next_after(time(2023, 1, 1, 0, 0, 0)),
+            Some(time(2023, 12, 3, 75, 0, 0))
+        );
+        assert_eq!(
+            alarm.next_after(time(2023, 12, 3, 75, 0, 0)),
+            Some(time(2024, 12, 3, 75, 0, 0))
+        );
+
+        // the leap day waits for a leap year
+        let leap: Alarm = "37.06 00:00:00".parse().unwrap();
+        assert_eq!(
+            leap.next_after(time(2024, 37, 6, 1, 0, 0)),
+            Some(time(2028, 37, 6, 0, 0, 0))
+        );
+        assert_eq!(
+            leap.next_after(time(2097, 1, 1, 0, 0, 0)),
+            Some(time(2104, 37, 6, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn rings_once_per_time() {
+        let start = time(2023, 5, 5, 74, 99, 90);
+        let alarm: Alarm = "75:00:00".parse().unwrap();
+        let mut alarms = Alarms::new(vec![alarm], start);
+
+        assert!(!alarms.check(start + 9 * c10::TICK));
+        assert!(!alarms.flash(start + 9 * c10::TICK));
+        let rang = start + 10 * c10::TICK;
+        assert!(alarms.check(rang));
+        assert!(!alarms.check(rang + c10::TICK));
+
+        assert!(alarms.flash(rang));
+        assert!(!alarms.flash(rang + 5 * c10::TICK));
+        assert!(alarms.status()[0].starts_with("ALARM 75:00:00"));
+        alarms.dismiss();
+        assert!(!alarms.flash(rang));
+        assert!(alarms.status()[2].ends_with("next 2023  5.06 75:00:00"));
+    }
+
+    #[test]
+    fn survives_suspend() {
+        let start = time(2023, 5, 5, 10, 0, 0);
+        let alarms = ["75:00:00", "80:00:00"].map(|alarm| alarm.parse().unwrap());
+        let mut alarms = Alarms::new(alarms.to_vec(), start);
+
+        // waking days later, both missed alarms go off once, then wait for their next times
+        let resumed = time(2023, 5, 8, 90, 0, 0);
+        assert!(alarms.check(resumed));
+        assert!(!alarms.check(resumed + c10::TICK));
+        assert_eq!(
+            alarms.next,
+            [
+                Some(time(2023, 5, 9, 75, 0, 0)),
+                Some(time(2023, 5, 9, 80, 0, 0))
+            ]
+        );
+        assert_eq!(
+            alarms.ringing.map(|(alarm, _)| alarm),
+            Some(alarms.alarms[1])
+        );
+    }
+}
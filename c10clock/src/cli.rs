@@ -0,0 +1,441 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Command-line arguments for the clock.
+
+use std::fmt;
+
+use crate::alarm::Alarm;
+use crate::font::Font;
+
+pub const USAGE: &str = "\
+Usage: c10clock [OPTIONS] [COMMAND]
+
+Commands:
+  stopwatch                count up from zero, taking laps
+  timer <II:CC:TT>         count down from a C10 duration (0:25:00 is 25 centivals)
+  alarm <[DD.dd ]II:CC:TT> go off at a time of day, optionally only on decaday DD, day dd
+  dual                     show C10 time next to conventional time, in UTC and local time
+  analog                   show the time on a dial with interval, centival and tick hands
+
+Options:
+  -1, --once               print the current C10 time and exit
+  -f, --format <FORMAT>    lay out the time with strftime-style specifiers
+                           (%Y year, %D decaday, %d day, %I interval, %C centival, %T tick, ...)
+  -u, --utc                show Coordinated Universal Time (default)
+  -l, --local              show local time
+  -n, --no-date            leave the date off the full-screen clock
+  -F, --font <FONT>        draw digits in the segment (default), block or plain font
+  -i, --interval <II:CC:TT>
+                           refresh every given C10 duration (default one tick, or a tenth
+                           of a tick for the stopwatch)
+  -a, --alarm <[DD.dd ]II:CC:TT>
+                           set another alarm (times are in the zone shown)
+  -e, --exec <COMMAND>     run COMMAND with sh when the timer runs out or an alarm goes off
+      --csv <FILE>         write the stopwatch's laps to FILE (- for standard output) on exit
+  -s, --stats              show the bytes written per frame on the bottom row
+  -h, --help               print this help and exit
+  -V, --version            print the version and exit
+
+Keys:
+  d                        show or hide the date
+  u                        switch between UTC and local time
+  f                        switch to the next font
+  space                    start, pause or resume the stopwatch or timer, or dismiss an alarm
+  l                        take a lap
+  r                        reset the stopwatch or restart the timer
+  q, Esc                   quit";
+
+/// What the program should do once arguments are parsed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Run the full-screen clock.
+    Run,
+    /// Print the current time once.
+    Once,
+    /// Run the full-screen stopwatch.
+    Stopwatch,
+    /// Count down from the given duration.
+    Timer(c10::Duration),
+    /// Show the clock until alarms go off.
+    Alarm,
+    /// Show C10 and conventional time side by side.
+    Dual,
+    /// Run the full-screen clock with an analog dial.
+    Analog,
+    Help,
+    Version,
+}
+
+/// Which time zone the clock displays.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Zone {
+    Utc,
+    Local,
+}
+
+impl Zone {
+    /// Returns the other zone.
+    pub fn toggle(self) -> Zone {
+        match self {
+            Zone::Utc => Zone::Local,
+            Zone::Local => Zone::Utc,
+        }
+    }
+
+    /// Returns `time` as read in this zone.
+    pub fn read(self, time: c10::SystemTime) -> c10::SystemTime {
+        match self {
+            Zone::Utc => time,
+            Zone::Local => time.to_local(),
+        }
+    }
+}
+
+/// The clock's configuration, as given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub action: Action,
+    pub format: Option<String>,
+    pub zone: Zone,
+    pub date: bool,
+    pub font: Font,
+    /// How often to refresh, if not the default for the action.
+    pub interval: Option<c10::Duration>,
+    pub stats: bool,
+    /// Where to write the stopwatch's laps on exit.
+    pub csv: Option<String>,
+    /// A shell command to run when the timer runs out or an alarm goes off.
+    pub exec: Option<String>,
+    pub alarms: Vec<Alarm>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            action: Action::Run,
+            format: None,
+            zone: Zone::Utc,
+            date: true,
+            font: Font::Segment,
+            interval: None,
+            stats: false,
+            csv: None,
+            exec: None,
+            alarms: Vec::new(),
+        }
+    }
+}
+
+impl Options {
+    /// Returns `time` in the selected zone and layout.
+    pub fn render(&self, time: c10::SystemTime) -> String {
+        let time = self.zoned(time);
+        match &self.format {
+            Some(format) => time.format(format).to_string(),
+            None => time.to_string(),
+        }
+    }
+
+    /// Returns `time` laid out for the full-screen clock: the time of day first, then the date
+    /// unless it is hidden.
+    pub fn render_lines(&self, time: c10::SystemTime) -> Vec<String> {
+        let time = self.zoned(time);
+        let mut lines = match &self.format {
+            Some(format) => time
+                .format(format)
+                .to_string()
+                .lines()
+                .map(String::from)
+                .collect(),
+            None => vec![
+                time.format("%I:%C:%T").to_string(),
+                time.format("%-Y %-D.%d").to_string(),
+            ],
+        };
+        if !self.date {
+            lines.truncate(1);
+        }
+        lines
+    }
+
+    /// Returns `time` as read in the selected zone.
+    pub fn zoned(&self, time: c10::SystemTime) -> c10::SystemTime {
+        self.zone.read(time)
+    }
+}
+
+/// A problem with the command-line arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        fmter.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Parses arguments (excluding the program name) into [`Options`].
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    // the argument that chose the action, so that a conflicting one can be reported
+    let mut chosen = None;
+
+    while let Some(arg) = args.next() {
+        // accept both `--flag value` and `--flag=value`
+        let (flag, mut inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_owned(), Some(value.to_owned()))
+            }
+            _ => (arg, None),
+        };
+        // taking the inline value, so that one left over belongs to a flag that takes none
+        let mut value = |name: &str| {
+            inline
+                .take()
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError(format!("{name} requires a value")))
+        };
+
+        match flag.as_str() {
+            "-1" | "--once" => choose(&mut options, &mut chosen, &flag, Action::Once)?,
+            "-f" | "--format" => options.format = Some(value("--format")?),
+            "-u" | "--utc" => options.zone = Zone::Utc,
+            "-l" | "--local" => options.zone = Zone::Local,
+            "-n" | "--no-date" => options.date = false,
+            "-F" | "--font" => {
+                let name = value("--font")?;
+                options.font = name
+                    .parse()
+                    .map_err(|()| CliError(format!("unknown --font {name:?}")))?;
+            }
+            "-i" | "--interval" => {
+                let text = value("--interval")?;
+                let interval: c10::Duration = text
+                    .parse()
+                    .map_err(|err| CliError(format!("invalid --interval {text:?}: {err}")))?;
+                if interval.is_zero() {
+                    return Err(CliError("--interval must be longer than zero".into()));
+                }
+                options.interval = Some(interval);
+            }
+            "-s" | "--stats" => options.stats = true,
+            "--csv" => options.csv = Some(value("--csv")?),
+            "-e" | "--exec" => options.exec = Some(value("--exec")?),
+            // the rest of the arguments are ignored
+            "-h" | "--help" => options.action = Action::Help,
+            "-V" | "--version" => options.action = Action::Version,
+            "stopwatch" => choose(&mut options, &mut chosen, &flag, Action::Stopwatch)?,
+            "dual" => choose(&mut options, &mut chosen, &flag, Action::Dual)?,
+            "analog" => choose(&mut options, &mut chosen, &flag, Action::Analog)?,
+            "alarm" | "-a" | "--alarm" => {
+                let text = value("alarm")?;
+                let alarm = text
+                    .parse()
+                    .map_err(|err| CliError(format!("invalid alarm {text:?}: {err}")))?;
+                choose(&mut options, &mut chosen, &flag, Action::Alarm)?;
+                options.alarms.push(alarm);
+            }
+            "timer" => {
+                let text = value("timer")?;
+                let duration: c10::Duration = text
+                    .parse()
+                    .map_err(|err| CliError(format!("invalid timer duration {text:?}: {err}")))?;
+                choose(&mut options, &mut chosen, &flag, Action::Timer(duration))?;
+            }
+            _ if !flag.starts_with('-') => {
+                return Err(CliError(format!("unrecognized command {flag:?}")))
+            }
+            _ => return Err(CliError(format!("unrecognized argument {flag:?}"))),
+        }
+        if inline.is_some() {
+            return Err(CliError(format!("{flag} takes no value")));
+        }
+        if matches!(options.action, Action::Help | Action::Version) {
+            return Ok(options);
+        }
+    }
+
+    Ok(options)
+}
+
+// sets the action chosen by `flag`, unless an earlier argument chose a different one
+fn choose(
+    options: &mut Options,
+    chosen: &mut Option<String>,
+    flag: &str,
+    action: Action,
+) -> Result<(), CliError> {
+    if let Some(earlier) = chosen {
+        if options.action != action {
+            return Err(CliError(format!(
+                "{flag} cannot be combined with {earlier}"
+            )));
+        }
+    }
+    *chosen = Some(flag.to_owned());
+    options.action = action;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Options, CliError> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(parse_args(&[]), Ok(Options::default()));
+    }
+
+    #[test]
+    fn flags() {
+        let options = parse_args(&[
+            "--once",
+            "--local",
+            "--format",
+            "%I:%C",
+            "--interval=00:01:00",
+        ]);
+        assert_eq!(
+            options,
+            Ok(Options {
+                action: Action::Once,
+                format: Some("%I:%C".into()),
+                zone: Zone::Local,
+                date: true,
+                font: Font::Segment,
+                interval: Some(c10::CENTIVAL),
+                stats: false,
+                csv: None,
+                exec: None,
+                alarms: Vec::new(),
+            })
+        );
+
+        let options = parse_args(&[
+            "-l", "-u", "-f", "%T", "-i", "0:0:5", "-F", "block", "-s", "-n",
+        ]);
+        assert_eq!(
+            options,
+            Ok(Options {
+                action: Action::Run,
+                format: Some("%T".into()),
+                zone: Zone::Utc,
+                date: false,
+                font: Font::Block,
+                interval: Some(5 * c10::TICK),
+                stats: true,
+                csv: None,
+                exec: None,
+                alarms: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn commands() {
+        let options = parse_args(&["stopwatch", "--csv", "laps.csv", "-F", "plain"]).unwrap();
+        assert_eq!(options.action, Action::Stopwatch);
+        assert_eq!(options.csv.as_deref(), Some("laps.csv"));
+        assert_eq!(options.font, Font::Plain);
+
+        let options = parse_args(&["timer", "0:25:00", "--exec", "notify-send done"]).unwrap();
+        assert_eq!(options.action, Action::Timer(25 * c10::CENTIVAL));
+        assert_eq!(options.exec.as_deref(), Some("notify-send done"));
+        assert_eq!(
+            parse_args(&["timer", "100:00:00"]).unwrap().action,
+            Action::Timer(c10::DAY)
+        );
+
+        let options = parse_args(&["alarm", "75:00:00", "-a", "12.03 50:00:00", "-l"]).unwrap();
+        assert_eq!(options.action, Action::Alarm);
+        assert_eq!(
+            options.alarms,
+            [
+                "75:00:00".parse().unwrap(),
+                "12.03 50:00:00".parse().unwrap()
+            ]
+        );
+
+        let options = parse_args(&["analog", "-n"]).unwrap();
+        assert_eq!(options.action, Action::Analog);
+        assert!(!options.date);
+
+        let options = parse_args(&["dual", "--local"]).unwrap();
+        assert_eq!(options.action, Action::Dual);
+        assert_eq!(options.zone, Zone::Local);
+    }
+
+    #[test]
+    fn help_and_version() {
+        assert_eq!(parse_args(&["-h", "--bogus"]).unwrap().action, Action::Help);
+        assert_eq!(parse_args(&["--version"]).unwrap().action, Action::Version);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse_args(&["--format"]).is_err());
+        assert!(parse_args(&["--interval", "soon"]).is_err());
+        assert!(parse_args(&["--interval", "00:00:00"]).is_err());
+        assert!(parse_args(&["--font", "comic"]).is_err());
+        assert!(parse_args(&["sundial"]).is_err());
+        assert!(parse_args(&["--csv"]).is_err());
+        assert!(parse_args(&["timer"]).is_err());
+        assert!(parse_args(&["timer", "soon"]).is_err());
+        assert!(parse_args(&["alarm", "99:99:100"]).is_err());
+        assert!(parse_args(&["--alarm"]).is_err());
+    }
+
+    #[test]
+    fn switches_take_no_value() {
+        for flag in [
+            "--once",
+            "--utc",
+            "--local",
+            "--no-date",
+            "--stats",
+            "--help",
+            "--version",
+        ] {
+            assert_eq!(
+                parse_args(&[&format!("{flag}=no")]),
+                Err(CliError(format!("{flag} takes no value")))
+            );
+        }
+        // even an empty one
+        assert!(parse_args(&["--once="]).is_err());
+        // while flags with a value still take it inline
+        assert_eq!(parse_args(&["--font=plain"]).unwrap().font, Font::Plain);
+    }
+
+    #[test]
+    fn conflicting_actions() {
+        assert_eq!(
+            parse_args(&["timer", "0:10:00", "--alarm", "50:00:00"]),
+            Err(CliError("--alarm cannot be combined with timer".into()))
+        );
+        assert_eq!(
+            parse_args(&["-a", "50:00:00", "stopwatch"]),
+            Err(CliError("stopwatch cannot be combined with -a".into()))
+        );
+        assert!(parse_args(&["--once", "dual"]).is_err());
+        assert!(parse_args(&["analog", "timer", "0:10:00"]).is_err());
+        assert!(parse_args(&["timer", "0:10:00", "timer", "0:20:00"]).is_err());
+
+        // repeating the same action is not a conflict
+        assert_eq!(
+            parse_args(&["alarm", "50:00:00", "--alarm", "75:00:00", "alarm", "80:00:00"])
+                .unwrap()
+                .alarms
+                .len(),
+            3
+        );
+        assert_eq!(parse_args(&["dual", "dual"]).unwrap().action, Action::Dual);
+    }
+}
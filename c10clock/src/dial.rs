@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! An analog clock face for the decimal day, drawn in braille dots.
+//!
+//! The dial is divided into ten numbered segments of ten marks each. The short, thick interval
+//! hand goes round once a day, the centival hand once an interval and the long, thin tick hand
+//! once a centival.
+
+use std::f64::consts::TAU;
+
+// radius in dots from which the hundred minor marks and the numbers are drawn
+const MINOR_MARKS: f64 = 24.0;
+const NUMBERS: f64 = 16.0;
+
+/// Draws the dial showing the time of day of `time`, as large as fits in `width` × `height`
+/// cells, returning one string per row. Returns no rows when there is no room for a dial.
+pub fn render(time: c10::SystemTime, width: usize, height: usize) -> Vec<String> {
+    let mut canvas = Canvas::new(width, height);
+    // braille dots are about as far apart across as down, so the dial is round in dots
+    let (cx, cy) = (width as f64, 2.0 * height as f64);
+    let radius = cx.min(cy) - 1.0;
+    if radius < 4.0 {
+        return Vec::new();
+    }
+    let at = |turn: f64, length: f64| {
+        let angle = TAU * turn;
+        (cx + length * angle.sin(), cy - length * angle.cos())
+    };
+
+    // the rim, with a mark every hundredth of a turn when there is room for them all
+    let steps = (TAU * radius * 2.0).ceil() as usize;
+    for step in 0..steps {
+        let (x, y) = at(step as f64 / steps as f64, radius);
+        canvas.set(x, y);
+    }
+    for mark in 0..100 {
+        let turn = f64::from(mark) / 100.0;
+        let inner = match mark % 10 {
+            0 => 0.85,
+            _ if radius >= MINOR_MARKS => 0.94,
+            _ => continue,
+        };
+        canvas.line(at(turn, radius * inner), at(turn, radius));
+    }
+
+    // the hands, each as long as given and going round once in the given time
+    let hands = [(c10::DAY, 0.5), (c10::INTERVAL, 0.75), (c10::CENTIVAL, 0.9)];
+    let day = time
+        .as_nanoticks()
+        .rem_euclid(c10::DAY.as_nanoticks() as i128) as u128;
+    for (i, (period, length)) in hands.into_iter().enumerate() {
+        let nanoticks = period.as_nanoticks();
+        let turn = (day % nanoticks) as f64 / nanoticks as f64;
+        let tip = at(turn, radius * length);
+        canvas.line((cx, cy), tip);
+        if i == 0 {
+            // thicken the interval hand across its length
+            let (dx, dy) = ((TAU * turn).cos() * 0.75, (TAU * turn).sin() * 0.75);
+            canvas.line((cx + dx, cy + dy), (tip.0 + dx, tip.1 + dy));
+            canvas.line((cx - dx, cy - dy), (tip.0 - dx, tip.1 - dy));
+        }
+    }
+
+    if radius >= NUMBERS {
+        for number in 0..10 {
+            let (x, y) = at(f64::from(number) / 10.0, radius * 0.72);
+            canvas.label(x, y, &(number * 10).to_string());
+        }
+    }
+    canvas.rows()
+}
+
+/// A grid of cells, each showing either 2 × 4 braille dots or a character of text.
+struct Canvas {
+    width: usize,
+    height: usize,
+    dots: Vec<u8>,
+    text: Vec<Option<char>>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            dots: vec![0; width * height],
+            text: vec![None; width * height],
+        }
+    }
+
+    // sets the dot nearest to (x, y), counted in dots from the top left, if on the canvas
+    fn set(&mut self, x: f64, y: f64) {
+        let (x, y) = (x.round(), y.round());
+        if x < 0.0 || y < 0.0 {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if x >= 2 * self.width || y >= 4 * self.height {
+            return;
+        }
+        // braille numbers its dots down the left column, then the right, then the bottom row
+        const BITS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        self.dots[y / 4 * self.width + x / 2] |= BITS[x % 2][y % 4];
+    }
+
+    fn line(&mut self, from: (f64, f64), to: (f64, f64)) {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = dx.abs().max(dy.abs()).ceil().max(1.0);
+        for step in 0..=steps as usize {
+            let along = step as f64 / steps;
+            self.set(from.0 + dx * along, from.1 + dy * along);
+        }
+    }
+
+    // writes `text` centered on the cell holding the dot at (x, y)
+    fn label(&mut self, x: f64, y: f64, text: &str) {
+        let row = (y / 4.0) as usize;
+        let len = text.chars().count();
+        let start = ((x / 2.0) as usize).saturating_sub(len / 2);
+        if row >= self.height || start + len > self.width {
+            return;
+        }
+        for (i, c) in text.chars().enumerate() {
+            self.text[row * self.width + start + i] = Some(c);
+        }
+    }
+
+    fn rows(&self) -> Vec<String> {
+        (0..self.height)
+            .map(|row| {
+                (row * self.width..(row + 1) * self.width)
+                    .map(|cell| match (self.text[cell], self.dots[cell]) {
+                        (Some(c), _) => c,
+                        (None, 0) => ' ',
+                        (None, dots) => char::from_u32(0x2800 + u32::from(dots)).unwrap_or(' '),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(ints: u64, cents: u64, ticks: u64) -> c10::SystemTime {
+        c10::SystemTime::from_c10_components(2023, 1, 1, ints, cents, ticks).unwrap()
+    }
+
+    // whether the cell at `row`, `col` has any dots set
+    fn dotted(rows: &[String], row: usize, col: usize) -> bool {
+        let c = rows[row].chars().nth(col).unwrap();
+        ('\u{2801}'..='\u{28ff}').contains(&c)
+    }
+
+    #[test]
+    fn braille_dots() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set(0.0, 0.0);
+        canvas.set(1.0, 3.0);
+        canvas.set(3.0, 1.0);
+        canvas.set(-1.0, 0.0);
+        canvas.set(4.0, 0.0);
+        assert_eq!(canvas.rows(), ["⢁⠐"]);
+
+        canvas.label(2.0, 0.0, "x");
+        assert_eq!(canvas.rows(), ["⢁x"]);
+    }
+
+    #[test]
+    fn fills_the_screen() {
+        for (width, height) in [(80, 24), (200, 60), (30, 40), (10, 3)] {
+            let rows = render(time(12, 34, 56), width, height);
+            assert_eq!(rows.len(), height);
+            assert!(rows.iter().all(|row| row.chars().count() == width));
+        }
+        assert!(render(time(12, 34, 56), 3, 1).is_empty());
+    }
+
+    #[test]
+    fn hands_point_at_the_time() {
+        // 40 × 20 cells is 80 × 80 dots, centered on the dot at (40, 40)
+        let (width, height) = (40, 20);
+
+        // at midday the interval hand points down and the others up
+        let rows = render(time(50, 0, 0), width, height);
+        assert!(dotted(&rows, 14, 20));
+        assert!(!dotted(&rows, 14, 30));
+        assert!(dotted(&rows, 5, 20));
+        assert_eq!(rows[2].chars().nth(20), Some('0'));
+        assert!(rows[17].contains("50"));
+
+        // a quarter of the way round each turn, the hands all point right
+        let rows = render(time(25, 25, 25), width, height);
+        assert!(dotted(&rows, 10, 27));
+        assert!(dotted(&rows, 10, 36));
+        assert!(!dotted(&rows, 14, 20));
+        assert!(!dotted(&rows, 5, 20));
+    }
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! C10 time side by side with conventional time, for learning to read it.
+
+use crate::cli::Zone;
+
+/// Returns the lines to draw for `time`: the C10 and conventional times of day in `zone` to draw
+/// large, then a table of both in UTC and local time, and how long each C10 unit lasts.
+pub fn render(time: c10::SystemTime, zone: Zone) -> (Vec<String>, Vec<String>) {
+    let zoned = zone.read(time);
+    let gregorian = zoned.to_gregorian();
+    let big = vec![
+        zoned.format("%I:%C:%T").to_string(),
+        format!(
+            "{:02}:{:02}:{:02}",
+            gregorian.hour, gregorian.minute, gregorian.second
+        ),
+    ];
+
+    let mut extra = vec![format!("{:<7}{:<23}{}", "", "C10", "conventional")];
+    for (name, zone) in [("UTC", Zone::Utc), ("local", Zone::Local)] {
+        let time = zone.read(time);
+        extra.push(format!(
+            "{name:<7}{:<23}{}",
+            time.to_string(),
+            time.to_gregorian()
+        ));
+    }
+    extra.push(String::new());
+    extra.push(hint());
+    extra.push("u switch zone   q quit".to_owned());
+    (big, extra)
+}
+
+/// Returns how long each C10 unit of the time of day lasts in conventional units.
+pub fn hint() -> String {
+    [
+        ("interval", c10::INTERVAL),
+        ("centival", c10::CENTIVAL),
+        ("tick", c10::TICK),
+    ]
+    .map(|(name, unit)| format!("1 {name} = {}", conventional(unit)))
+    .join("   ")
+}
+
+// formats `duration` in the largest of minutes, seconds and milliseconds that it reaches
+fn conventional(duration: c10::Duration) -> String {
+    let secs = std::time::Duration::from(duration).as_secs_f64();
+    match secs {
+        _ if secs >= 60.0 => format!("{} min", secs / 60.0),
+        _ if secs >= 1.0 => format!("{secs} s"),
+        _ => format!("{} ms", secs * 1_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hint_from_units() {
+        assert_eq!(
+            hint(),
+            "1 interval = 14.4 min   1 centival = 8.64 s   1 tick = 86.4 ms"
+        );
+        assert_eq!(conventional(c10::DAY), "1440 min");
+    }
+
+    #[test]
+    fn side_by_side() {
+        // 2000-02-29 12:00:00 UTC is exactly 50 intervals into the day
+        let time = c10::SystemTime::from_unix_seconds(951_825_600).unwrap();
+        let (big, extra) = render(time, Zone::Utc);
+        assert_eq!(big, ["50:00:00", "12:00:00"]);
+        assert_eq!(
+            extra[0].trim_end(),
+            "       C10                    conventional"
+        );
+        assert_eq!(
+            extra[1],
+            "UTC    2000  6.10 50:00:00    2000-02-29 12:00:00"
+        );
+        assert!(extra[2].starts_with("local  "));
+        assert_eq!(extra[4], hint());
+    }
+}
@@ -0,0 +1,381 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Large digits for reading the clock from across a room.
+
+use std::str::FromStr;
+
+/// How the clock draws its text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Font {
+    /// Ordinary terminal text.
+    Plain,
+    /// Seven-segment digits drawn with line characters.
+    Segment,
+    /// Digits drawn from solid blocks on a 3×5 grid.
+    Block,
+}
+
+// 3×5 bitmaps for the block font, one string per row
+const BLOCK_GLYPHS: [(char, [&str; 5]); 14] = [
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["###", "..#", "###", "#..", "###"]),
+    ('3', ["###", "..#", "###", "..#", "###"]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "###", "..#", "###"]),
+    ('6', ["###", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", "..#", "..#", "..#"]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "###"]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    (':', [".", "#", ".", "#", "."]),
+    ('.', [".", ".", ".", ".", "#"]),
+    (' ', ["..", "..", "..", "..", ".."]),
+];
+
+// seven-segment layout: a (top), b (upper right), c (lower right), d (bottom), e (lower left),
+// f (upper left) and g (middle), as bits 0 through 6
+const SEGMENTS: [(char, u8); 11] = [
+    ('0', 0b011_1111),
+    ('1', 0b000_0110),
+    ('2', 0b101_1011),
+    ('3', 0b100_1111),
+    ('4', 0b110_0110),
+    ('5', 0b110_1101),
+    ('6', 0b111_1101),
+    ('7', 0b000_0111),
+    ('8', 0b111_1111),
+    ('9', 0b110_1111),
+    ('-', 0b100_0000),
+];
+
+const BLOCK: char = '█';
+const DOT: char = '•';
+const HORIZONTAL: char = '━';
+const VERTICAL: char = '┃';
+
+type Glyph = Vec<Vec<char>>;
+
+impl Font {
+    /// Returns the font after this one, for cycling through them.
+    pub fn next(self) -> Font {
+        match self {
+            Font::Plain => Font::Segment,
+            Font::Segment => Font::Block,
+            Font::Block => Font::Plain,
+        }
+    }
+
+    /// Returns the height in rows of text drawn at `scale`.
+    pub fn height(self, scale: usize) -> usize {
+        match self {
+            Font::Plain => 1,
+            Font::Segment => 2 * scale + 3,
+            Font::Block => 5 * scale,
+        }
+    }
+
+    /// Returns the width in columns of `text` drawn at `scale`, or `None` if the font lacks one
+    /// of its characters.
+    pub fn width(self, text: &str, scale: usize) -> Option<usize> {
+        if self == Font::Plain {
+            return Some(text.chars().count());
+        }
+        let mut width = 0;
+        for (i, c) in text.chars().enumerate() {
+            if i > 0 {
+                width += self.gap(scale);
+            }
+            width += self.glyph_width(c, scale)?;
+        }
+        Some(width)
+    }
+
+    /// Draws `text` at `scale`, returning one string per row, or `None` if the font lacks one of
+    /// its characters.
+    pub fn render(self, text: &str, scale: usize) -> Option<Vec<String>> {
+        if self == Font::Plain {
+            return Some(vec![text.to_owned()]);
+        }
+        let mut rows = vec![String::new(); self.height(scale)];
+        for (i, c) in text.chars().enumerate() {
+            let glyph = self.glyph(c, scale)?;
+            for (row, line) in rows.iter_mut().zip(glyph) {
+                if i > 0 {
+                    row.extend(std::iter::repeat_n(' ', self.gap(scale)));
+                }
+                row.extend(line);
+            }
+        }
+        Some(rows)
+    }
+
+    fn gap(self, scale: usize) -> usize {
+        match self {
+            Font::Plain => 0,
+            Font::Segment => scale.div_ceil(2),
+            Font::Block => scale,
+        }
+    }
+
+    fn glyph_width(self, c: char, scale: usize) -> Option<usize> {
+        match self {
+            Font::Plain => Some(1),
+            Font::Segment => match c {
+                ':' | '.' => Some(1),
+                ' ' => Some(scale),
+                _ => SEGMENTS
+                    .iter()
+                    .any(|&(g, _)| g == c)
+                    .then_some(2 * scale + 2),
+            },
+            Font::Block => {
+                let (_, rows) = BLOCK_GLYPHS.iter().find(|&&(g, _)| g == c)?;
+                Some(rows[0].len() * 2 * scale)
+            }
+        }
+    }
+
+    fn glyph(self, c: char, scale: usize) -> Option<Glyph> {
+        let width = self.glyph_width(c, scale)?;
+        let height = self.height(scale);
+        let mut glyph = vec![vec![' '; width]; height];
+
+        match self {
+            Font::Plain => glyph[0][0] = c,
+            Font::Segment => match c {
+                // dots line up with the middle of the upper and lower vertical segments
+                ':' => {
+                    glyph[1 + (scale - 1) / 2][0] = DOT;
+                    glyph[scale + 2 + (scale - 1) / 2][0] = DOT;
+                }
+                '.' => glyph[height - 1][0] = DOT,
+                ' ' => {}
+                _ => {
+                    let (_, mask) = SEGMENTS.iter().find(|&&(g, _)| g == c)?;
+                    let lit = |segment: u32| mask & (1 << segment) != 0;
+                    let (right, middle, bottom) = (width - 1, scale + 1, height - 1);
+
+                    for (segment, row) in [(0, 0), (6, middle), (3, bottom)] {
+                        if lit(segment) {
+                            glyph[row][1..right].fill(HORIZONTAL);
+                        }
+                    }
+                    for (segment, col, rows) in [
+                        (5, 0, 1..middle),
+                        (1, right, 1..middle),
+                        (4, 0, middle + 1..bottom),
+                        (2, right, middle + 1..bottom),
+                    ] {
+                        if lit(segment) {
+                            for row in rows {
+                                glyph[row][col] = VERTICAL;
+                            }
+                        }
+                    }
+                }
+            },
+            Font::Block => {
+                let (_, rows) = BLOCK_GLYPHS.iter().find(|&&(g, _)| g == c)?;
+                for (y, line) in glyph.iter_mut().enumerate() {
+                    for (x, cell) in line.iter_mut().enumerate() {
+                        if rows[y / scale].as_bytes()[x / (2 * scale)] == b'#' {
+                            *cell = BLOCK;
+                        }
+                    }
+                }
+            }
+        }
+        Some(glyph)
+    }
+}
+
+impl FromStr for Font {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "plain" => Ok(Font::Plain),
+            "segment" => Ok(Font::Segment),
+            "block" => Ok(Font::Block),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Lays out `lines` centered on a `width` × `height` screen, returning one string per screen row.
+///
+/// The first line is drawn as large as will fit and the rest at half its scale beneath it, each
+/// separated by a blank row. Lines fall back to plain text when the font lacks one of their
+/// characters, and everything does when the screen is too small for even the smallest scale.
+///
+/// The `extra` lines follow as in [`arrange`].
+pub fn layout(
+    font: Font,
+    lines: &[String],
+    extra: &[String],
+    width: usize,
+    height: usize,
+) -> Vec<String> {
+    let draw = |top| {
+        (1..=top)
+            .rev()
+            .find_map(|scale| fit(font, lines, scale, width, top))
+            .unwrap_or_else(|| lines.to_vec())
+    };
+    arrange(draw, extra, width, height)
+}
+
+/// Lays out the block of rows returned by `draw` centered on a `width` × `height` screen,
+/// returning one string per screen row. `draw` is given the number of rows left for it.
+///
+/// The `extra` lines follow in plain text, aligned with one another, taking up to half of the
+/// screen; those that do not fit are left off the end.
+pub fn arrange(
+    draw: impl FnOnce(usize) -> Vec<String>,
+    extra: &[String],
+    width: usize,
+    height: usize,
+) -> Vec<String> {
+    let reserved = match extra.len() {
+        0 => 0,
+        n => (n + 1).min(height / 2),
+    };
+    let top = height - reserved;
+    let block = draw(top);
+
+    let mut screen = vec![String::new(); top.saturating_sub(block.len()) / 2];
+    for row in block.into_iter().take(top) {
+        let indent = width.saturating_sub(row.chars().count()) / 2;
+        screen.push(indented(indent, &row));
+    }
+    screen.resize(top, String::new());
+
+    if reserved > 0 {
+        screen.push(String::new());
+        let widest = extra.iter().map(|row| row.chars().count()).max();
+        let indent = width.saturating_sub(widest.unwrap_or(0)) / 2;
+        for row in &extra[..reserved - 1] {
+            screen.push(indented(indent, row));
+        }
+    }
+    screen
+}
+
+fn indented(indent: usize, row: &str) -> String {
+    let row = row.trim_end();
+    match row.is_empty() {
+        true => String::new(),
+        false => format!("{:indent$}{row}", ""),
+    }
+}
+
+// draws `lines` with the first at `scale`, if all of them fit on the screen
+fn fit(
+    font: Font,
+    lines: &[String],
+    scale: usize,
+    width: usize,
+    height: usize,
+) -> Option<Vec<String>> {
+    let mut block: Vec<String> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let scale = if i == 0 { scale } else { (scale / 2).max(1) };
+        let (font, scale) = match font.width(line, scale) {
+            Some(_) => (font, scale),
+            None => (Font::Plain, 1),
+        };
+        if font.width(line, scale)? > width {
+            return None;
+        }
+        if i > 0 {
+            block.push(String::new());
+        }
+        block.extend(font.render(line, scale)?);
+    }
+    (block.len() <= height).then_some(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_digits() {
+        let rows = Font::Segment.render("8:1", 1).unwrap();
+        assert_eq!(
+            rows,
+            [
+                " ━━        ",
+                "┃  ┃ •    ┃",
+                " ━━        ",
+                "┃  ┃ •    ┃",
+                " ━━        ",
+            ]
+        );
+        assert_eq!(Font::Segment.width("8:1", 1), Some(11));
+        assert_eq!(Font::Segment.height(2), 7);
+        assert_eq!(Font::Segment.render("0", 2).unwrap()[1], "┃    ┃");
+    }
+
+    #[test]
+    fn block_digits() {
+        let rows = Font::Block.render("7.", 1).unwrap();
+        assert_eq!(
+            rows,
+            [
+                "██████   ",
+                "    ██   ",
+                "    ██   ",
+                "    ██   ",
+                "    ██ ██",
+            ]
+        );
+        assert_eq!(Font::Block.render("1", 2).unwrap().len(), 10);
+        assert_eq!(Font::Block.width("00:00", 2), Some(4 * 12 + 4 + 4 * 2));
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(Font::Segment.render("a", 1), None);
+        assert_eq!(Font::Block.width("12x", 1), None);
+        assert_eq!(Font::Plain.render("abc", 5).unwrap(), ["abc"]);
+    }
+
+    #[test]
+    fn layout_fits_and_centers() {
+        let lines = ["12:34:56".to_owned(), "2023 1.01".to_owned()];
+        for (width, height) in [(80, 24), (200, 60), (40, 12), (20, 5), (5, 1)] {
+            for font in [Font::Plain, Font::Segment, Font::Block] {
+                let screen = layout(font, &lines, &[], width, height);
+                assert_eq!(screen.len(), height);
+                assert!(screen.iter().all(|row| row.chars().count() <= width.max(9)));
+            }
+        }
+
+        let screen = layout(Font::Plain, &lines, &[], 20, 5);
+        assert_eq!(screen, ["", "      12:34:56", "", "     2023 1.01", ""]);
+
+        // the largest scale that fits is chosen
+        let screen = layout(Font::Segment, &lines[..1], &[], 200, 60);
+        let rows = screen.iter().filter(|row| !row.is_empty()).count();
+        assert_eq!(rows, Font::Segment.height(12));
+        assert_eq!(Font::Segment.width("12:34:56", 12), Some(200));
+    }
+
+    #[test]
+    fn layout_extra_lines() {
+        let lines = ["12:34".to_owned()];
+        let extra = ["status".to_owned(), "a".into(), "b".into(), "c".into()];
+
+        let screen = layout(Font::Plain, &lines, &extra, 12, 8);
+        assert_eq!(
+            screen,
+            ["", "   12:34", "", "", "", "   status", "   a", "   b"]
+        );
+
+        // the extra lines never take more than half of the screen
+        let screen = layout(Font::Plain, &lines, &extra, 12, 4);
+        assert_eq!(screen, ["   12:34", "", "", "   status"]);
+    }
+}
@@ -4,86 +4,430 @@
 
 #![deny(warnings)]
 
-use std::io::Write;
-use std::thread::sleep;
+mod alarm;
+mod cli;
+mod dial;
+mod dual;
+mod font;
+mod schedule;
+mod screen;
+mod stopwatch;
+mod timer;
+
+use std::fs::File;
+use std::process::{Child, Command, ExitCode, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::{cursor, terminal, ExecutableCommand, Result};
 
-struct UI {
-    stdout: std::io::Stdout,
+use alarm::Alarms;
+use cli::{Action, Options};
+use schedule::Schedule;
+use screen::{Frame, Screen};
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use stopwatch::Stopwatch;
+use timer::Timer;
 
-    // timing variables for screen update
-    clk_time: Instant,
-    drifts_ns: [i64; 4],
-    drifts_idx: usize,
+// longest wait between checks for a quit signal: crossterm restarts a poll that a signal
+// interrupts, so the flag the signal sets is only seen once the poll times out
+const SIGNAL_CHECK: Duration = Duration::from_millis(50);
+
+/// What the full-screen UI shows.
+enum Face {
+    Clock,
+    Stopwatch(Stopwatch),
+    Timer(Timer),
+    Alarm(Alarms),
+    Dual,
+    Analog,
 }
 
-impl UI {
-    pub fn new() -> Self {
+impl Face {
+    // how often the face changes, unless given on the command line
+    fn interval(&self) -> c10::Duration {
+        match self {
+            Face::Clock | Face::Timer(_) | Face::Alarm(_) | Face::Dual | Face::Analog => c10::TICK,
+            Face::Stopwatch(_) => c10::TICK / 10,
+        }
+    }
+}
+
+/// What a key press asks of the UI.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Hotkey {
+    Quit,
+    NextFont,
+    ToggleDate,
+    ToggleZone,
+    StartPause,
+    Lap,
+    Reset,
+    Dismiss,
+}
+
+impl Hotkey {
+    // the hotkey `key` stands for on `face`, if any
+    fn of(face: &Face, key: KeyEvent) -> Option<Hotkey> {
+        Some(match (face, key.code) {
+            (Face::Clock | Face::Analog, KeyCode::Char('d')) => Hotkey::ToggleDate,
+            (Face::Clock | Face::Dual | Face::Analog, KeyCode::Char('u')) => Hotkey::ToggleZone,
+            (Face::Stopwatch(_) | Face::Timer(_), KeyCode::Char(' ')) => Hotkey::StartPause,
+            (Face::Stopwatch(_), KeyCode::Char('l')) => Hotkey::Lap,
+            (Face::Stopwatch(_) | Face::Timer(_), KeyCode::Char('r')) => Hotkey::Reset,
+            (Face::Alarm(_), KeyCode::Char(' ')) => Hotkey::Dismiss,
+            (_, KeyCode::Char('q') | KeyCode::Esc) => Hotkey::Quit,
+            // raw mode delivers Ctrl-C as a key rather than a signal
+            (_, KeyCode::Char('c')) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Hotkey::Quit
+            }
+            (_, KeyCode::Char('f')) => Hotkey::NextFont,
+            _ => return None,
+        })
+    }
+}
+
+/// Puts the terminal in raw mode on the alternate screen, and restores it when dropped.
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        // from here on, dropping the guard undoes whatever succeeded
+        let guard = Terminal;
         let mut stdout = std::io::stdout();
-        stdout.execute(cursor::Hide).unwrap();
+        stdout.execute(terminal::EnterAlternateScreen)?;
+        stdout.execute(cursor::Hide)?;
+        Ok(guard)
+    }
+}
 
-        Self {
-            stdout,
-            clk_time: Instant::now(),
-            drifts_ns: Default::default(),
-            drifts_idx: Default::default(),
-        }
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+        let mut stdout = std::io::stdout();
+        let _ = stdout.execute(cursor::Show);
+        let _ = stdout.execute(terminal::LeaveAlternateScreen);
     }
+}
+
+struct UI {
+    screen: Screen<std::io::Stdout>,
+    options: Options,
+    face: Face,
+    size: (u16, u16),
+    // set by a key press or a signal to leave the main loop
+    quit: Arc<AtomicBool>,
+    schedule: Schedule,
+    // commands started by `--exec` that have yet to finish
+    hooks: Vec<Child>,
+    // a message for the bottom of the screen
+    notice: Option<String>,
+    // restores the terminal once everything above is dropped
+    _terminal: Terminal,
+}
 
-    pub fn run(&mut self) -> Result<()> {
-        loop {
-            // clear the screen
-            self.stdout
-                .execute(terminal::Clear(terminal::ClearType::All))?;
-            self.stdout.execute(cursor::MoveTo(0, 0))?;
+impl UI {
+    pub fn new(options: Options, face: Face) -> Result<Self> {
+        let quit = Arc::new(AtomicBool::new(false));
+        for signal in [SIGINT, SIGTERM, SIGHUP] {
+            signal_hook::flag::register(signal, Arc::clone(&quit))?;
+        }
 
-            let c10now = c10::SystemTime::now();
+        let terminal = Terminal::enter()?;
+        Ok(Self {
+            screen: Screen::new(std::io::stdout()),
+            size: terminal::size().unwrap_or((80, 24)),
+            quit,
+            schedule: Schedule::new(options.interval.unwrap_or(face.interval())),
+            options,
+            face,
+            hooks: Vec::new(),
+            notice: None,
+            _terminal: terminal,
+        })
+    }
+
+    /// Runs until asked to quit, returning the face as it was left.
+    pub fn run(mut self) -> Result<Face> {
+        // when the face next changes by its own reckoning rather than the clock's
+        let mut face_due = None;
+        while !self.quit.load(Ordering::Relaxed) {
+            let now = c10::SystemTime::now();
+            let due = self.schedule.due(now, self.options.zoned(now));
+            let alert = self.alert()?;
+            let changed = face_due.is_some_and(|at| Instant::now() >= at);
+            if due.is_some() || alert || changed {
+                self.draw(due.unwrap_or(now))?;
+            }
 
-            // write to the screen
-            writeln!(self.stdout, "{c10now}")?;
-            self.stdout.flush()?;
+            // sleep until the next time should be printed, or the face changes before then
+            let instant = Instant::now();
+            let change = match &self.face {
+                Face::Timer(timer) => timer.until_change(instant),
+                _ => None,
+            };
+            face_due = change.map(|wait| instant + wait);
+            let now = c10::SystemTime::now();
+            let wait = self.schedule.until_next(self.options.zoned(now));
+            self.wait(change.map_or(wait, |change| change.min(wait)))?;
+        }
+        Ok(std::mem::replace(&mut self.face, Face::Clock))
+    }
 
-            // sleep until the next time should be printed
-            self.sleep();
+    // rings if the timer has run out or an alarm has come due, returning whether either did
+    fn alert(&mut self) -> Result<bool> {
+        // alarms are checked against the wall clock, which jumps ahead on resuming from suspend
+        let wall = self.options.zoned(c10::SystemTime::now());
+        let alert = match &mut self.face {
+            Face::Timer(timer) => timer.expire(Instant::now()),
+            Face::Alarm(alarms) => alarms.check(wall),
+            _ => false,
+        };
+        if alert {
+            self.screen.bell()?;
+            if let Some(command) = &self.options.exec {
+                self.run_hook(command.clone());
+            }
         }
+        self.hooks
+            .retain_mut(|child| matches!(child.try_wait(), Ok(None)));
+        Ok(alert)
     }
 
-    fn sleep(&mut self) {
-        // GOAL is one "tick" of duration (10^-6 of a day)
-        const GOAL: Duration = Duration::from_micros(24 * 60 * 60);
+    fn draw(&mut self, time: c10::SystemTime) -> Result<()> {
+        let (width, height) = self.size;
+        let now = Instant::now();
+        let wall = self.options.zoned(c10::SystemTime::now());
+        let (lines, mut extra) = match &self.face {
+            Face::Clock => (self.options.render_lines(time), Vec::new()),
+            Face::Stopwatch(watch) => watch.render(now),
+            Face::Timer(timer) => timer.render(now),
+            Face::Alarm(alarms) => (self.options.render_lines(time), alarms.status()),
+            Face::Dual => dual::render(time, self.options.zone),
+            Face::Analog => (
+                Vec::new(),
+                vec![self.options.render_lines(time).join("   ")],
+            ),
+        };
+        extra.extend(self.notice.clone());
+        let (width, height) = (usize::from(width), usize::from(height));
+        let lines = match &self.face {
+            Face::Analog => {
+                let time = self.options.zoned(time);
+                font::arrange(
+                    |rows| dial::render(time, width, rows),
+                    &extra,
+                    width,
+                    height,
+                )
+            }
+            _ => font::layout(self.options.font, &lines, &extra, width, height),
+        };
+        let mut frame = Frame::from_lines(&lines, width, height);
+        frame.set_reverse(match &self.face {
+            Face::Timer(timer) => timer.flash(now),
+            Face::Alarm(alarms) => alarms.flash(wall),
+            _ => false,
+        });
 
-        // "how long since the last loop iteration"
-        let elapsed = self.clk_time.elapsed();
-        self.clk_time += elapsed;
+        if self.options.stats {
+            let stats = self.screen.stats();
+            let average = stats.total.checked_div(stats.frames).unwrap_or(0);
+            let text = format!(
+                "last frame {} B, average {average} B, max {} B",
+                stats.last, stats.max
+            );
+            frame.put(height.saturating_sub(1), 0, &text);
+        }
 
-        // compute the average error from the "goal" sleep duration as measured by elapsed time
-        let drift = (elapsed.as_nanos() as i64) - (GOAL.as_nanos() as i64);
+        // write only what changed to the screen
+        self.screen.draw(&frame)?;
+        Ok(())
+    }
 
-        // store drifts in a rolling circular buffer
-        self.drifts_ns[self.drifts_idx] = drift;
-        self.drifts_idx = (self.drifts_idx + 1) % self.drifts_ns.len();
+    // starts `command` in the background, out of the way of the screen
+    fn run_hook(&mut self, command: String) {
+        let child = Command::new("sh")
+            .args(["-c", &command])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            Ok(child) => self.hooks.push(child),
+            Err(err) => self.notice = Some(format!("could not run {command:?}: {err}")),
+        }
+    }
+
+    // waits for `timeout`, handling key presses and redrawing straight away whenever the terminal
+    // is resized or a setting changes
+    fn wait(&mut self, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        while !self.quit.load(Ordering::Relaxed) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !event::poll(remaining.min(SIGNAL_CHECK))? {
+                if remaining <= SIGNAL_CHECK {
+                    break;
+                }
+                continue;
+            }
+            match event::read()? {
+                Event::Resize(width, height) => {
+                    self.size = (width, height);
+                    self.screen.invalidate();
+                }
+                Event::Key(key) if key.kind != KeyEventKind::Release => {
+                    if !self.key(key) {
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+            if !self.quit.load(Ordering::Relaxed) {
+                self.draw(c10::SystemTime::now())?;
+            }
+        }
+        Ok(())
+    }
 
-        let avg_drift: i64 = self.drifts_ns.iter().sum::<i64>() / self.drifts_ns.len() as i64;
-        //eprintln!("avg_drift {:?}", avg_drift);
+    // acts on a hotkey, returning whether it was one
+    fn key(&mut self, key: KeyEvent) -> bool {
+        let Some(hotkey) = Hotkey::of(&self.face, key) else {
+            return false;
+        };
+        let now = Instant::now();
+        match (hotkey, &mut self.face) {
+            (Hotkey::Quit, _) => self.quit.store(true, Ordering::Relaxed),
+            (Hotkey::NextFont, _) => self.options.font = self.options.font.next(),
+            (Hotkey::ToggleDate, _) => self.options.date = !self.options.date,
+            (Hotkey::ToggleZone, _) => self.options.zone = self.options.zone.toggle(),
+            (Hotkey::StartPause, Face::Stopwatch(watch)) => watch.toggle(now),
+            (Hotkey::StartPause, Face::Timer(timer)) => timer.toggle(now),
+            (Hotkey::Lap, Face::Stopwatch(watch)) => watch.lap(now),
+            (Hotkey::Reset, Face::Stopwatch(watch)) => watch.reset(),
+            (Hotkey::Reset, Face::Timer(timer)) => timer.restart(now),
+            (Hotkey::Dismiss, Face::Alarm(alarms)) => alarms.dismiss(),
+            _ => {}
+        }
+        true
+    }
+}
 
-        // sleep for the adjusted amount of time accounting for average drift
-        let computed_sleep = (GOAL.as_nanos() as i64) - avg_drift;
-        //eprintln!("sleeping for: {:?}", computed_sleep);
+fn main() -> Result<ExitCode> {
+    let options = match cli::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("c10clock: {err}\n\n{}", cli::USAGE);
+            return Ok(ExitCode::from(2));
+        }
+    };
 
-        sleep(Duration::from_nanos(computed_sleep.try_into().unwrap()));
+    match options.action {
+        Action::Help => println!("{}", cli::USAGE),
+        Action::Version => println!("c10clock {}", env!("CARGO_PKG_VERSION")),
+        Action::Once => println!("{}", options.render(c10::SystemTime::now())),
+        Action::Run => drop(UI::new(options, Face::Clock)?.run()?),
+        Action::Dual => drop(UI::new(options, Face::Dual)?.run()?),
+        Action::Analog => drop(UI::new(options, Face::Analog)?.run()?),
+        Action::Timer(duration) => {
+            let timer = Timer::new(duration, Instant::now());
+            drop(UI::new(options, Face::Timer(timer))?.run()?);
+        }
+        Action::Alarm => {
+            let now = options.zoned(c10::SystemTime::now());
+            let alarms = Alarms::new(options.alarms.clone(), now);
+            drop(UI::new(options, Face::Alarm(alarms))?.run()?);
+        }
+        Action::Stopwatch => {
+            let face = UI::new(options.clone(), Face::Stopwatch(Stopwatch::new()))?.run()?;
+            if let Face::Stopwatch(watch) = face {
+                report_laps(&watch, options.csv.as_deref())?;
+            }
+        }
     }
+    Ok(ExitCode::SUCCESS)
 }
 
-// RAII cleanup of the terminal (undoes the init in UI::new())
-impl Drop for UI {
-    fn drop(&mut self) {
-        std::io::stdout().execute(cursor::Show).unwrap();
+// prints the stopwatch's laps once the terminal is restored, as a table or as CSV
+fn report_laps(watch: &Stopwatch, csv: Option<&str>) -> Result<()> {
+    match csv {
+        Some("-") => watch.write_csv(std::io::stdout().lock())?,
+        Some(path) => watch.write_csv(File::create(path)?)?,
+        None => {}
+    }
+    if watch.laps().next().is_some() && csv != Some("-") {
+        for line in watch.table() {
+            println!("{line}");
+        }
     }
+    Ok(())
 }
 
-fn main() -> Result<()> {
-    let mut ui = UI::new();
-    ui.run()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(face: &Face, code: KeyCode) -> Option<Hotkey> {
+        Hotkey::of(face, KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn faces() -> Vec<Face> {
+        let now = c10::SystemTime::UNIX_EPOCH;
+        vec![
+            Face::Clock,
+            Face::Stopwatch(Stopwatch::new()),
+            Face::Timer(Timer::new(c10::CENTIVAL, Instant::now())),
+            Face::Alarm(Alarms::new(Vec::new(), now)),
+            Face::Dual,
+            Face::Analog,
+        ]
+    }
+
+    #[test]
+    fn common_hotkeys() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        for face in faces() {
+            assert_eq!(press(&face, KeyCode::Char('q')), Some(Hotkey::Quit));
+            assert_eq!(press(&face, KeyCode::Esc), Some(Hotkey::Quit));
+            assert_eq!(Hotkey::of(&face, ctrl_c), Some(Hotkey::Quit));
+            assert_eq!(press(&face, KeyCode::Char('c')), None);
+            assert_eq!(press(&face, KeyCode::Char('f')), Some(Hotkey::NextFont));
+            assert_eq!(press(&face, KeyCode::Char('x')), None);
+            assert_eq!(press(&face, KeyCode::Enter), None);
+        }
+    }
+
+    #[test]
+    fn face_hotkeys() {
+        use Hotkey::*;
+        let [clock, stopwatch, timer, alarm, dual, analog] =
+            <[Face; 6]>::try_from(faces()).unwrap_or_else(|_| unreachable!());
+        // what 'd', 'u', space, 'l' and 'r' do on each face
+        let keys = ['d', 'u', ' ', 'l', 'r'];
+        let expected = [
+            (
+                &clock,
+                [Some(ToggleDate), Some(ToggleZone), None, None, None],
+            ),
+            (
+                &analog,
+                [Some(ToggleDate), Some(ToggleZone), None, None, None],
+            ),
+            (&dual, [None, Some(ToggleZone), None, None, None]),
+            (
+                &stopwatch,
+                [None, None, Some(StartPause), Some(Lap), Some(Reset)],
+            ),
+            (&timer, [None, None, Some(StartPause), None, Some(Reset)]),
+            (&alarm, [None, None, Some(Dismiss), None, None]),
+        ];
+        for (face, hotkeys) in expected {
+            for (key, hotkey) in keys.into_iter().zip(hotkeys) {
+                assert_eq!(press(face, KeyCode::Char(key)), hotkey, "{key:?}");
+            }
+        }
+    }
 }
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Deciding when the clock face should change.
+
+use std::time::Duration;
+
+/// Phase-locks redraws to the boundaries of a refresh interval on the displayed clock, counted
+/// from its epoch.
+///
+/// The schedule is given the time rather than reading it, so that it can be driven by a fake
+/// clock. It is also given the time as displayed, since a local clock is offset from UTC by a
+/// whole number of seconds rather than of ticks, and redraws must line up with what it shows.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    interval: i128,
+    // the interval (counted from the displayed epoch) that was last shown
+    shown: Option<i128>,
+}
+
+impl Schedule {
+    /// Returns a schedule for refreshing every `interval`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    pub fn new(interval: c10::Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "refresh interval must be longer than zero"
+        );
+        Schedule {
+            interval: interval.as_nanoticks() as i128,
+            shown: None,
+        }
+    }
+
+    /// Returns the instant of the boundary the displayed time `shown` falls after if its frame
+    /// has yet to be shown, marking it shown. `shown` is `now` as read on the displayed clock.
+    ///
+    /// Returns `None` when woken early, before the next boundary. Boundaries are skipped only if
+    /// the caller slept past more than one, and are shown again if the wall clock is set back.
+    pub fn due(&mut self, now: c10::SystemTime, shown: c10::SystemTime) -> Option<c10::SystemTime> {
+        let index = shown.as_nanoticks().div_euclid(self.interval);
+        if self.shown == Some(index) {
+            return None;
+        }
+        self.shown = Some(index);
+        let past = shown.as_nanoticks().rem_euclid(self.interval);
+        Some(c10::SystemTime::from_nanoticks(now.as_nanoticks() - past).unwrap_or(now))
+    }
+
+    /// Returns how long after the displayed time `shown` the next boundary comes, rounded up to a
+    /// whole nanosecond so that waking after that long is never early.
+    pub fn until_next(&self, shown: c10::SystemTime) -> Duration {
+        let nanoticks = self.interval - shown.as_nanoticks().rem_euclid(self.interval);
+        // 1 nanotick = 54/625 nanoseconds
+        let nanos = (nanoticks as u128 * 54).div_ceil(625);
+        Duration::from_nanos(nanos.try_into().unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a wall clock that only moves when asked to, displayed `offset` ahead of UTC
+    struct FakeClock {
+        now: c10::SystemTime,
+        offset: c10::Duration,
+    }
+
+    impl FakeClock {
+        fn new(now: c10::SystemTime) -> Self {
+            FakeClock {
+                now,
+                offset: c10::Duration::ZERO,
+            }
+        }
+
+        fn shown(&self) -> c10::SystemTime {
+            self.now + self.offset
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += c10::Duration::try_from(duration).unwrap();
+        }
+    }
+
+    // runs the schedule for `frames` frames, returning the boundaries (in nanoticks) it showed
+    // as displayed
+    fn run(
+        clock: &mut FakeClock,
+        schedule: &mut Schedule,
+        frames: usize,
+        mut oversleep: impl FnMut() -> Duration,
+    ) -> Vec<i128> {
+        let mut shown = Vec::new();
+        while shown.len() < frames {
+            if let Some(time) = schedule.due(clock.now, clock.shown()) {
+                shown.push((time + clock.offset).as_nanoticks());
+            }
+            let wait = schedule.until_next(clock.shown());
+            clock.sleep(wait + oversleep());
+        }
+        shown
+    }
+
+    // checks that `shown` steps through every boundary of `interval` from the one before `start`
+    fn assert_every_boundary(shown: &[i128], start: c10::SystemTime, interval: c10::Duration) {
+        let step = interval.as_nanoticks() as i128;
+        assert_eq!(shown[0], start.as_nanoticks() / step * step);
+        for pair in shown.windows(2) {
+            assert_eq!(pair[1] - pair[0], step);
+        }
+    }
+
+    #[test]
+    fn waits_for_the_boundary() {
+        let schedule = Schedule::new(c10::TICK);
+        let boundary = c10::SystemTime::from_ticks(1_000).unwrap();
+
+        assert_eq!(schedule.until_next(boundary), Duration::from_micros(86_400));
+        assert_eq!(
+            schedule.until_next(boundary - c10::Duration::from_nanoticks(1)),
+            Duration::from_nanos(1)
+        );
+        assert_eq!(
+            schedule.until_next(boundary + c10::Duration::from_nanoticks(500_000_000)),
+            Duration::from_micros(43_200)
+        );
+
+        // before the epoch too
+        let before = c10::SystemTime::from_ticks(-1).unwrap() + c10::Duration::from_nanoticks(1);
+        assert_eq!(schedule.until_next(before), Duration::from_micros(86_400));
+        let before = c10::SystemTime::from_ticks(-1).unwrap() + c10::TICK / 4;
+        assert_eq!(schedule.until_next(before), Duration::from_micros(64_800));
+
+        let centivals = Schedule::new(c10::CENTIVAL);
+        assert_eq!(centivals.until_next(boundary), Duration::from_millis(8_640));
+    }
+
+    #[test]
+    fn no_ticks_skipped_or_repeated() {
+        // fractions of an interval to oversleep by, up to just under one
+        let fractions = [0.0, 0.5, 0.999, 0.1, 0.75, 0.01, 0.3];
+        for interval in [c10::TICK, 5 * c10::TICK, c10::CENTIVAL] {
+            let start = c10::SystemTime::from_ticks(123_456_789).unwrap()
+                + c10::Duration::from_nanoticks(987_654_321);
+            let mut clock = FakeClock::new(start);
+            let mut schedule = Schedule::new(interval);
+
+            let mut fractions = fractions.iter().cycle();
+            let shown = run(&mut clock, &mut schedule, 1_000, || {
+                Duration::from(interval).mul_f64(*fractions.next().unwrap())
+            });
+            assert_every_boundary(&shown, start, interval);
+        }
+    }
+
+    #[test]
+    fn boundaries_follow_the_displayed_clock() {
+        // an hour is not a whole number of ticks, so local boundaries fall mid-tick in UTC
+        let hour = c10::Duration::try_from(Duration::from_secs(3_600)).unwrap();
+        assert_ne!(hour.as_nanoticks() % c10::TICK.as_nanoticks(), 0);
+
+        for interval in [c10::TICK, c10::CENTIVAL] {
+            let start = c10::SystemTime::from_ticks(123_456_789).unwrap();
+            let mut clock = FakeClock {
+                now: start,
+                offset: hour,
+            };
+            let mut schedule = Schedule::new(interval);
+
+            let shown = run(&mut clock, &mut schedule, 100, || Duration::ZERO);
+            assert_every_boundary(&shown, start + hour, interval);
+
+            // waking when told to lands on the displayed boundary, give or take the nanosecond
+            // (about 12 nanoticks) that waits are rounded up to, and not on a UTC one
+            let step = interval.as_nanoticks() as i128;
+            assert!(clock.shown().as_nanoticks() % step < 12);
+            assert!(clock.now.as_nanoticks() % step >= 12);
+        }
+    }
+
+    #[test]
+    fn early_wakeups_are_not_repeated() {
+        let mut clock = FakeClock::new(c10::SystemTime::UNIX_EPOCH);
+        let mut schedule = Schedule::new(c10::TICK);
+        assert!(schedule.due(clock.now, clock.shown()).is_some());
+
+        // a timer that fires a little early gives nothing to draw, then a short wait
+        clock.sleep(schedule.until_next(clock.shown()) - Duration::from_micros(10));
+        assert_eq!(schedule.due(clock.now, clock.shown()), None);
+        assert!(schedule.until_next(clock.shown()) < Duration::from_micros(11));
+        clock.sleep(schedule.until_next(clock.shown()));
+        assert_eq!(clock.now.as_ticks(), 1);
+        assert_eq!(
+            schedule.due(clock.now, clock.shown()),
+            c10::SystemTime::from_ticks(1).ok()
+        );
+    }
+
+    #[test]
+    fn oversleeping_and_clock_changes() {
+        let mut clock = FakeClock::new(c10::SystemTime::UNIX_EPOCH);
+        let mut schedule = Schedule::new(c10::TICK);
+        let due =
+            |schedule: &mut Schedule, clock: &FakeClock| schedule.due(clock.now, clock.shown());
+        assert_eq!(due(&mut schedule, &clock).unwrap().as_ticks(), 0);
+
+        // sleeping through several ticks jumps to the latest, once
+        clock.sleep(Duration::from_millis(300));
+        assert_eq!(due(&mut schedule, &clock).unwrap().as_ticks(), 3);
+        assert_eq!(due(&mut schedule, &clock), None);
+
+        // setting the wall clock back shows the earlier tick again
+        clock.now = c10::SystemTime::from_ticks(1).unwrap();
+        assert_eq!(due(&mut schedule, &clock).unwrap().as_ticks(), 1);
+        assert_eq!(due(&mut schedule, &clock), None);
+    }
+}
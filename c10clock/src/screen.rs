@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Double-buffered terminal output that only rewrites what changed.
+
+use std::io::{self, Write};
+
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, queue, terminal};
+
+// rewriting this many unchanged bytes is about as cheap as moving the cursor past them
+const MOVE_COST: usize = 8;
+
+/// The contents of the terminal for one frame, one character per cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<char>,
+    // whether the whole frame is shown in reverse video
+    reverse: bool,
+}
+
+impl Frame {
+    /// Returns a blank `width` × `height` frame.
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame {
+            width,
+            height,
+            cells: vec![' '; width * height],
+            reverse: false,
+        }
+    }
+
+    /// Returns a frame holding `lines` from the top-left corner, cut off at its edges.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S], width: usize, height: usize) -> Self {
+        let mut frame = Frame::new(width, height);
+        for (row, line) in lines.iter().enumerate().take(height) {
+            frame.put(row, 0, line.as_ref());
+        }
+        frame
+    }
+
+    /// Writes `text` into row `row` starting at column `col`, cut off at the right edge.
+    pub fn put(&mut self, row: usize, col: usize, text: &str) {
+        if row >= self.height || col >= self.width {
+            return;
+        }
+        let start = row * self.width;
+        let cells = &mut self.cells[start + col..start + self.width];
+        for (cell, c) in cells.iter_mut().zip(text.chars()) {
+            *cell = c;
+        }
+    }
+
+    /// Shows the whole frame in reverse video, or not.
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = reverse;
+    }
+
+    fn row(&self, row: usize) -> &[char] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+}
+
+/// How many bytes frames have taken to draw.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub frames: u64,
+    pub total: u64,
+    pub last: usize,
+    pub max: usize,
+}
+
+/// A terminal that remembers what it shows, so drawing the next frame only sends the difference.
+pub struct Screen<W: Write> {
+    out: W,
+    // what the terminal currently shows, or `None` if unknown (so the next frame repaints fully)
+    front: Option<Frame>,
+    stats: Stats,
+}
+
+impl<W: Write> Screen<W> {
+    pub fn new(out: W) -> Self {
+        Screen {
+            out,
+            front: None,
+            stats: Stats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Forgets what the terminal shows, so that the next frame is drawn in full.
+    pub fn invalidate(&mut self) {
+        self.front = None;
+    }
+
+    /// Brings the terminal up to date with `frame`, returning the number of bytes written.
+    ///
+    /// Only changed cells are rewritten, unless repainting everything would be shorter, which
+    /// bounds each frame by the size of a full repaint.
+    pub fn draw(&mut self, frame: &Frame) -> io::Result<usize> {
+        let mut buf = Vec::new();
+        let diffed = match &self.front {
+            Some(front)
+                if front.width == frame.width
+                    && front.height == frame.height
+                    && front.reverse == frame.reverse =>
+            {
+                diff(&mut buf, front, frame)?;
+                true
+            }
+            _ => false,
+        };
+        if !diffed || buf.len() > repaint_len(frame) {
+            buf.clear();
+            repaint(&mut buf, frame)?;
+        }
+
+        self.out.write_all(&buf)?;
+        self.out.flush()?;
+        self.front = Some(frame.clone());
+
+        self.stats.frames += 1;
+        self.stats.total += buf.len() as u64;
+        self.stats.last = buf.len();
+        self.stats.max = self.stats.max.max(buf.len());
+        Ok(buf.len())
+    }
+
+    /// Rings the terminal bell.
+    pub fn bell(&mut self) -> io::Result<()> {
+        self.out.write_all(b"\x07")?;
+        self.out.flush()
+    }
+}
+
+// clears the screen and writes every row of `frame`
+fn repaint(buf: &mut Vec<u8>, frame: &Frame) -> io::Result<()> {
+    queue!(buf, terminal::Clear(terminal::ClearType::All))?;
+    if frame.reverse {
+        // blank cells have to be written as well to show up reversed
+        queue!(buf, SetAttribute(Attribute::Reverse))?;
+    }
+    for row in 0..frame.height {
+        let line: String = frame.row(row).iter().collect();
+        let line = match frame.reverse {
+            true => &line,
+            false => line.trim_end(),
+        };
+        if !line.is_empty() {
+            queue!(buf, cursor::MoveTo(0, row as u16), Print(line))?;
+        }
+    }
+    if frame.reverse {
+        queue!(buf, SetAttribute(Attribute::Reset))?;
+    }
+    Ok(())
+}
+
+fn repaint_len(frame: &Frame) -> usize {
+    let mut buf = Vec::new();
+    repaint(&mut buf, frame).map_or(usize::MAX, |()| buf.len())
+}
+
+// writes the cells of `frame` that differ from `front`, which is shown with the same attributes
+fn diff(buf: &mut Vec<u8>, front: &Frame, frame: &Frame) -> io::Result<()> {
+    let begin = buf.len();
+    if frame.reverse {
+        queue!(buf, SetAttribute(Attribute::Reverse))?;
+    }
+    let start = buf.len();
+
+    // where the terminal's cursor is, if known
+    let mut cursor: Option<(usize, usize)> = None;
+
+    for row in 0..frame.height {
+        let (old, new) = (front.row(row), frame.row(row));
+        for col in 0..frame.width {
+            if old[col] == new[col] {
+                continue;
+            }
+
+            // either move the cursor here or rewrite the unchanged cells in between
+            let skipped = match cursor {
+                Some((r, c)) if r == row && c <= col => {
+                    new[c..col].iter().map(|c| c.len_utf8()).sum()
+                }
+                _ => usize::MAX,
+            };
+            if skipped > MOVE_COST {
+                queue!(buf, cursor::MoveTo(col as u16, row as u16))?;
+            } else if let Some((_, c)) = cursor {
+                buf.extend(new[c..col].iter().collect::<String>().bytes());
+            }
+
+            let mut utf8 = [0; 4];
+            buf.extend_from_slice(new[col].encode_utf8(&mut utf8).as_bytes());
+
+            // the cursor's position after the last column depends on the terminal
+            cursor = (col + 1 < frame.width).then_some((row, col + 1));
+        }
+    }
+
+    if frame.reverse {
+        match buf.len() == start {
+            true => buf.truncate(begin),
+            false => queue!(buf, SetAttribute(Attribute::Reset))?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(lines: &[&str]) -> Frame {
+        Frame::from_lines(lines, 10, 3)
+    }
+
+    #[test]
+    fn from_lines() {
+        let frame = Frame::from_lines(&["abc", "0123456789abc"], 5, 3);
+        assert_eq!(frame.row(0), ['a', 'b', 'c', ' ', ' ']);
+        assert_eq!(frame.row(1), ['0', '1', '2', '3', '4']);
+        assert_eq!(frame.row(2), [' '; 5]);
+    }
+
+    #[test]
+    fn first_frame_repaints() {
+        let mut screen = Screen::new(Vec::new());
+        let bytes = screen.draw(&frame(&["12:34", "", "ab"])).unwrap();
+        assert_eq!(
+            String::from_utf8(screen.out.clone()).unwrap(),
+            "\x1b[2J\x1b[1;1H12:34\x1b[3;1Hab"
+        );
+        assert_eq!(bytes, screen.stats().last);
+    }
+
+    #[test]
+    fn only_changes_are_written() {
+        let mut screen = Screen::new(Vec::new());
+        screen.draw(&frame(&["12:34:56"])).unwrap();
+        screen.out.clear();
+
+        assert_eq!(screen.draw(&frame(&["12:34:56"])).unwrap(), 0);
+
+        screen.draw(&frame(&["12:34:57"])).unwrap();
+        assert_eq!(String::from_utf8(screen.out.clone()).unwrap(), "\x1b[1;8H7");
+        screen.out.clear();
+
+        // nearby changes are joined by rewriting the cells in between
+        screen.draw(&frame(&["12:35:08"])).unwrap();
+        assert_eq!(
+            String::from_utf8(screen.out.clone()).unwrap(),
+            "\x1b[1;5H5:08"
+        );
+        screen.out.clear();
+
+        // changes far apart move the cursor instead
+        screen
+            .draw(&frame(&["02:35:08", "", "         x"]))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(screen.out.clone()).unwrap(),
+            "\x1b[1;1H0\x1b[3;10Hx"
+        );
+    }
+
+    #[test]
+    fn reverse_video() {
+        let mut screen = Screen::new(Vec::new());
+        let mut frame = Frame::from_lines(&["ab"], 4, 2);
+        screen.draw(&frame).unwrap();
+        screen.out.clear();
+
+        // switching to reverse video repaints every cell
+        frame.set_reverse(true);
+        screen.draw(&frame).unwrap();
+        assert_eq!(
+            String::from_utf8(screen.out.clone()).unwrap(),
+            "\x1b[2J\x1b[7m\x1b[1;1Hab  \x1b[2;1H    \x1b[0m"
+        );
+        screen.out.clear();
+
+        // and changes while reversed stay reversed
+        assert_eq!(screen.draw(&frame).unwrap(), 0);
+        frame.put(1, 3, "x");
+        screen.draw(&frame).unwrap();
+        assert_eq!(
+            String::from_utf8(screen.out.clone()).unwrap(),
+            "\x1b[7m\x1b[2;4Hx\x1b[0m"
+        );
+        screen.out.clear();
+
+        frame.set_reverse(false);
+        screen.draw(&frame).unwrap();
+        assert!(screen.out.starts_with(b"\x1b[2J\x1b[1;1Hab"));
+
+        screen.out.clear();
+        screen.bell().unwrap();
+        assert_eq!(screen.out, b"\x07");
+    }
+
+    #[test]
+    fn bounded_by_repaint() {
+        let mut screen = Screen::new(Vec::new());
+        let full = screen
+            .draw(&frame(&["0123456789", "0123456789", "0123456789"]))
+            .unwrap();
+
+        // changing every other cell costs no more than repainting
+        let bytes = screen
+            .draw(&frame(&["a1b3c5d7e9", "a1b3c5d7e9", "a1b3c5d7e9"]))
+            .unwrap();
+        assert!(bytes <= full, "{bytes} > {full}");
+        assert_eq!(screen.stats().max, full);
+        assert_eq!(screen.stats().frames, 2);
+
+        // blanking the screen is cheaper by clearing it
+        screen.out.clear();
+        assert_eq!(screen.draw(&frame(&[])).unwrap(), 4);
+        assert_eq!(screen.out, b"\x1b[2J");
+
+        // a different size or an invalidated screen is repainted
+        screen.out.clear();
+        screen.draw(&Frame::from_lines(&["x"], 4, 1)).unwrap();
+        assert!(screen.out.starts_with(b"\x1b[2J"));
+        screen.out.clear();
+        screen.invalidate();
+        screen.draw(&Frame::from_lines(&["x"], 4, 1)).unwrap();
+        assert!(screen.out.starts_with(b"\x1b[2J"));
+    }
+}
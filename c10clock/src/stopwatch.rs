@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! A stopwatch counting in C10 units.
+
+use std::io::{self, Write};
+use std::time::Instant;
+
+/// Sub-tick digits shown on the stopwatch.
+const DIGITS: usize = 2;
+
+/// A stopwatch that can be paused and resumed and that records laps.
+///
+/// Time is measured on the monotonic clock, so changes to the wall clock do not affect it. Every
+/// method that needs the current time takes it as `now`.
+#[derive(Debug, Clone)]
+pub struct Stopwatch {
+    // time counted before the current run
+    banked: c10::Duration,
+    // when the current run started, if running
+    started: Option<Instant>,
+    // the total elapsed at each lap
+    laps: Vec<c10::Duration>,
+}
+
+/// One recorded lap.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Lap {
+    /// The lap's number, counting from 1.
+    pub number: usize,
+    /// The time since the previous lap (or the start).
+    pub split: c10::Duration,
+    /// The time since the start.
+    pub total: c10::Duration,
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Stopwatch::new()
+    }
+}
+
+impl Stopwatch {
+    pub fn new() -> Self {
+        Stopwatch {
+            banked: c10::Duration::ZERO,
+            started: None,
+            laps: Vec::new(),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Returns the total time counted as of `now`.
+    pub fn elapsed(&self, now: Instant) -> c10::Duration {
+        let run = match self.started {
+            Some(started) => now.saturating_duration_since(started),
+            None => std::time::Duration::ZERO,
+        };
+        // a run would have to last longer than the age of the universe to overflow
+        self.banked
+            .saturating_add(c10::Duration::try_from(run).unwrap_or(c10::Duration::MAX))
+    }
+
+    /// Starts or resumes counting if paused, and pauses if running.
+    pub fn toggle(&mut self, now: Instant) {
+        match self.started {
+            Some(_) => {
+                self.banked = self.elapsed(now);
+                self.started = None;
+            }
+            None => self.started = Some(now),
+        }
+    }
+
+    /// Records a lap at `now`, if running.
+    pub fn lap(&mut self, now: Instant) {
+        if self.is_running() {
+            self.laps.push(self.elapsed(now));
+        }
+    }
+
+    /// Stops the stopwatch, setting it back to zero and forgetting its laps.
+    pub fn reset(&mut self) {
+        *self = Stopwatch::new();
+    }
+
+    /// Returns the recorded laps, oldest first.
+    pub fn laps(&self) -> impl DoubleEndedIterator<Item = Lap> + '_ {
+        self.laps.iter().enumerate().map(|(i, &total)| {
+            let previous = i
+                .checked_sub(1)
+                .map_or(c10::Duration::ZERO, |i| self.laps[i]);
+            Lap {
+                number: i + 1,
+                split: total - previous,
+                total,
+            }
+        })
+    }
+
+    /// Returns the lines to draw at `now`: the elapsed time to draw large, and beneath it a status
+    /// line and the table of laps, newest first.
+    pub fn render(&self, now: Instant) -> (Vec<String>, Vec<String>) {
+        let status = match (self.is_running(), self.elapsed(now).is_zero()) {
+            (true, _) => "running",
+            (false, true) => "ready",
+            (false, false) => "paused",
+        };
+        let mut extra = vec![format!(
+            "{status:<8} space start/pause   l lap   r reset   q quit"
+        )];
+        if !self.laps.is_empty() {
+            extra.push(String::new());
+            extra.push(header());
+            extra.extend(self.laps().rev().map(row));
+        }
+        (vec![show(self.elapsed(now), DIGITS)], extra)
+    }
+
+    /// Returns the laps as a table with a header line, oldest first.
+    pub fn table(&self) -> Vec<String> {
+        std::iter::once(header())
+            .chain(self.laps().map(row))
+            .collect()
+    }
+
+    /// Writes the laps as comma-separated values, with times both in `II:CC:TT.fffffffff` form
+    /// and as decimal ticks.
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "lap,split,total,split_ticks,total_ticks")?;
+        for lap in self.laps() {
+            writeln!(
+                out,
+                "{},{:.9},{:.9},{},{}",
+                lap.number,
+                lap.split,
+                lap.total,
+                decimal_ticks(lap.split),
+                decimal_ticks(lap.total)
+            )?;
+        }
+        out.flush()
+    }
+}
+
+/// Formats `duration` with `digits` sub-tick digits, counting whole days separately since a
+/// duration's intervals wrap at 100.
+pub fn show(duration: c10::Duration, digits: usize) -> String {
+    match duration.as_ticks() / c10::DAY.as_ticks() {
+        0 => format!("{duration:.digits$}"),
+        days => format!("{days} {duration:.digits$}"),
+    }
+}
+
+fn header() -> String {
+    format!("{:>4}  {:>14}  {:>14}", "lap", "split", "total")
+}
+
+fn row(lap: Lap) -> String {
+    format!(
+        "{:>4}  {:>14}  {:>14}",
+        lap.number,
+        show(lap.split, DIGITS),
+        show(lap.total, DIGITS)
+    )
+}
+
+fn decimal_ticks(duration: c10::Duration) -> String {
+    format!(
+        "{}.{:09}",
+        duration.as_ticks(),
+        duration.subtick_nanoticks()
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    // a C10 duration as a standard one
+    pub(crate) fn to_std(duration: c10::Duration) -> Duration {
+        Duration::from(duration)
+    }
+
+    #[test]
+    fn start_pause_resume() {
+        let t0 = Instant::now();
+        let mut watch = Stopwatch::new();
+        assert!(!watch.is_running());
+        assert_eq!(
+            watch.elapsed(t0 + to_std(c10::CENTIVAL)),
+            c10::Duration::ZERO
+        );
+
+        watch.toggle(t0);
+        assert!(watch.is_running());
+        assert_eq!(watch.elapsed(t0 + to_std(5 * c10::TICK)), 5 * c10::TICK);
+
+        // paused time does not count
+        watch.toggle(t0 + to_std(10 * c10::TICK));
+        assert_eq!(watch.elapsed(t0 + to_std(c10::INTERVAL)), 10 * c10::TICK);
+        watch.toggle(t0 + to_std(c10::INTERVAL));
+        assert_eq!(
+            watch.elapsed(t0 + to_std(c10::INTERVAL + 3 * c10::TICK)),
+            13 * c10::TICK
+        );
+
+        watch.reset();
+        assert!(!watch.is_running());
+        assert_eq!(
+            watch.elapsed(t0 + to_std(c10::INTERVAL)),
+            c10::Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn laps() {
+        let t0 = Instant::now();
+        let mut watch = Stopwatch::new();
+
+        // laps are only taken while running
+        watch.lap(t0);
+        watch.toggle(t0);
+        watch.lap(t0 + to_std(c10::CENTIVAL));
+        watch.lap(t0 + to_std(c10::CENTIVAL + 25 * c10::TICK));
+        watch.toggle(t0 + to_std(2 * c10::CENTIVAL));
+        watch.lap(t0 + to_std(3 * c10::CENTIVAL));
+
+        let laps: Vec<Lap> = watch.laps().collect();
+        assert_eq!(
+            laps,
+            [
+                Lap {
+                    number: 1,
+                    split: c10::CENTIVAL,
+                    total: c10::CENTIVAL
+                },
+                Lap {
+                    number: 2,
+                    split: 25 * c10::TICK,
+                    total: c10::CENTIVAL + 25 * c10::TICK
+                },
+            ]
+        );
+
+        assert_eq!(
+            watch.table(),
+            [
+                " lap           split           total",
+                "   1     00:01:00.00     00:01:00.00",
+                "   2     00:00:25.00     00:01:25.00",
+            ]
+        );
+
+        let (big, extra) = watch.render(t0 + to_std(3 * c10::CENTIVAL));
+        assert_eq!(big, ["00:02:00.00"]);
+        assert!(extra[0].starts_with("paused"));
+        assert!(extra[3].starts_with("   2"));
+        assert!(extra[4].starts_with("   1"));
+    }
+
+    #[test]
+    fn csv() {
+        let t0 = Instant::now();
+        let mut watch = Stopwatch::new();
+        watch.toggle(t0);
+        watch.lap(t0 + to_std(c10::INTERVAL + c10::TICK / 2));
+
+        let mut out = Vec::new();
+        watch.write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "lap,split,total,split_ticks,total_ticks\n\
+             1,01:00:00.500000000,01:00:00.500000000,10000.500000000,10000.500000000\n"
+        );
+    }
+
+    #[test]
+    fn days_do_not_wrap() {
+        assert_eq!(show(c10::DAY + c10::TICK, 2), "1 00:00:01.00");
+        assert_eq!(show(c10::TICK / 4, 2), "00:00:00.25");
+        assert_eq!(show(c10::TICK / 4, 0), "00:00:00");
+    }
+}
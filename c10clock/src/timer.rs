@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! A countdown timer.
+
+use std::time::{Duration, Instant};
+
+use crate::stopwatch::{self, Stopwatch};
+
+/// Ticks the display spends in each phase of flashing once the timer expires.
+pub const FLASH_TICKS: u64 = 5;
+
+/// Counts down from a duration, flashing once it runs out until acknowledged.
+///
+/// Like [`Stopwatch`], which it counts with, every method that needs the current time takes it
+/// as `now`.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    duration: c10::Duration,
+    watch: Stopwatch,
+    // whether the expiry has been announced
+    fired: bool,
+    // whether the expiry has been seen, so flashing stops
+    acknowledged: bool,
+}
+
+impl Timer {
+    /// Returns a timer for `duration`, started at `now`.
+    pub fn new(duration: c10::Duration, now: Instant) -> Self {
+        let mut watch = Stopwatch::new();
+        watch.toggle(now);
+        Timer {
+            duration,
+            watch,
+            fired: false,
+            acknowledged: false,
+        }
+    }
+
+    /// Returns the time left as of `now`.
+    pub fn remaining(&self, now: Instant) -> c10::Duration {
+        self.duration.saturating_sub(self.watch.elapsed(now))
+    }
+
+    /// Returns whether the timer has newly run out as of `now`, which is true only once per run.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let expired = !self.fired && self.remaining(now).is_zero();
+        self.fired |= expired;
+        expired
+    }
+
+    /// Returns whether the display should be inverted at `now`, which it is every other few
+    /// ticks from expiry until acknowledged.
+    pub fn flash(&self, now: Instant) -> bool {
+        if !self.fired || self.acknowledged {
+            return false;
+        }
+        let over = self.watch.elapsed(now).saturating_sub(self.duration);
+        (over.as_ticks() / FLASH_TICKS).is_multiple_of(2)
+    }
+
+    /// Returns how long after `now` the display next changes of its own accord: the time left
+    /// drops a tick, the timer runs out, or the flashing turns over. Returns `None` while it
+    /// stands still, paused or acknowledged.
+    ///
+    /// This follows the timer's own start rather than the wall clock, so the caller can wake for
+    /// the expiry when it happens instead of at the next tick of the clock.
+    pub fn until_change(&self, now: Instant) -> Option<Duration> {
+        if !self.watch.is_running() || self.acknowledged {
+            return None;
+        }
+        let tick = c10::TICK.as_nanoticks();
+        let left = match self.fired {
+            false => match self.remaining(now).as_nanoticks() % tick {
+                0 => tick,
+                left => left,
+            },
+            true => {
+                let over = self.watch.elapsed(now).saturating_sub(self.duration);
+                let period = FLASH_TICKS as u128 * tick;
+                period - over.as_nanoticks() % period
+            }
+        };
+        // rounded up, so that waking after that long is never early
+        Some(Duration::from(c10::Duration::from_nanoticks(left)) + Duration::from_nanos(1))
+    }
+
+    /// Pauses or resumes the countdown, or stops the flashing once run out.
+    pub fn toggle(&mut self, now: Instant) {
+        match self.fired {
+            true => self.acknowledged = true,
+            false => self.watch.toggle(now),
+        }
+    }
+
+    /// Starts counting down the full duration again from `now`.
+    pub fn restart(&mut self, now: Instant) {
+        *self = Timer::new(self.duration, now);
+    }
+
+    /// Returns the lines to draw at `now`: the time left to draw large, then a status line.
+    pub fn render(&self, now: Instant) -> (Vec<String>, Vec<String>) {
+        let remaining = self.remaining(now);
+        let (status, keys) = match (self.fired, self.watch.is_running()) {
+            (true, _) => ("done", "space stop flashing"),
+            (false, true) => ("running", "space pause"),
+            (false, false) => ("paused", "space resume"),
+        };
+
+        // count whole ticks up, so that the display reaches zero as the timer runs out
+        let ticks = remaining.as_ticks() + u64::from(remaining.subtick_nanoticks() > 0);
+        let shown = stopwatch::show(c10::Duration::from_ticks(ticks), 0);
+
+        let extra = vec![format!(
+            "{status:<8} {} of {}   {keys}   r restart   q quit",
+            shown,
+            stopwatch::show(self.duration, 0),
+        )];
+        (vec![shown], extra)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stopwatch::tests::to_std;
+
+    #[test]
+    fn counts_down() {
+        let t0 = Instant::now();
+        let mut timer = Timer::new(25 * c10::CENTIVAL, t0);
+        assert_eq!(timer.remaining(t0), 25 * c10::CENTIVAL);
+        assert_eq!(
+            timer.remaining(t0 + to_std(c10::CENTIVAL)),
+            24 * c10::CENTIVAL
+        );
+        assert!(!timer.expire(t0 + to_std(c10::CENTIVAL)));
+
+        // paused time does not count
+        timer.toggle(t0 + to_std(c10::CENTIVAL));
+        assert_eq!(
+            timer.remaining(t0 + to_std(c10::INTERVAL)),
+            24 * c10::CENTIVAL
+        );
+        timer.toggle(t0 + to_std(c10::INTERVAL));
+        assert_eq!(
+            timer.remaining(t0 + to_std(c10::INTERVAL + 4 * c10::CENTIVAL)),
+            20 * c10::CENTIVAL
+        );
+    }
+
+    #[test]
+    fn shows_whole_ticks_left() {
+        let t0 = Instant::now();
+        let timer = Timer::new(c10::CENTIVAL, t0);
+        assert_eq!(timer.render(t0).0, ["00:01:00"]);
+        assert_eq!(timer.render(t0 + to_std(c10::TICK / 2)).0, ["00:01:00"]);
+        assert_eq!(timer.render(t0 + to_std(c10::TICK)).0, ["00:00:99"]);
+        assert_eq!(timer.render(t0 + to_std(c10::CENTIVAL)).0, ["00:00:00"]);
+        assert!(timer.render(t0).1[0].starts_with("running  00:01:00 of 00:01:00"));
+    }
+
+    #[test]
+    fn expires_once_and_flashes() {
+        let t0 = Instant::now();
+        let end = t0 + to_std(10 * c10::TICK);
+        let mut timer = Timer::new(10 * c10::TICK, t0);
+        assert!(!timer.flash(end));
+        assert!(timer.expire(end));
+        assert!(!timer.expire(end + to_std(c10::TICK)));
+
+        assert!(timer.flash(end));
+        assert!(timer.flash(end + to_std(4 * c10::TICK)));
+        assert!(!timer.flash(end + to_std(5 * c10::TICK)));
+        assert!(timer.flash(end + to_std(10 * c10::TICK)));
+        assert!(timer.render(end).1[0].starts_with("done"));
+
+        // acknowledging stops the flashing and restarting counts down again
+        timer.toggle(end);
+        assert!(!timer.flash(end + to_std(10 * c10::TICK)));
+        timer.restart(end);
+        assert_eq!(timer.remaining(end), 10 * c10::TICK);
+        assert!(timer.expire(end + to_std(10 * c10::TICK)));
+    }
+
+    #[test]
+    fn wakes_for_each_change() {
+        let t0 = Instant::now();
+        let end = t0 + to_std(10 * c10::TICK);
+        let mut timer = Timer::new(10 * c10::TICK, t0);
+        let nano = Duration::from_nanos(1);
+        assert_eq!(timer.until_change(t0), Some(to_std(c10::TICK) + nano));
+        assert_eq!(
+            timer.until_change(t0 + to_std(c10::TICK / 4)),
+            Some(to_std(3 * c10::TICK / 4) + nano)
+        );
+
+        // the last wait before running out ends just as it does, not at a tick of the clock
+        let before = end - to_std(c10::TICK / 3);
+        let wait = timer.until_change(before).unwrap();
+        assert!(!timer.expire(before));
+        assert!(timer.expire(before + wait));
+
+        // then the flashing turns over every few ticks, until acknowledged
+        assert_eq!(
+            timer.until_change(end + to_std(c10::TICK)),
+            Some(to_std((FLASH_TICKS - 1) * c10::TICK) + nano)
+        );
+        timer.toggle(end);
+        assert_eq!(timer.until_change(end), None);
+
+        // nothing changes while paused
+        timer.restart(end);
+        timer.toggle(end);
+        assert_eq!(timer.until_change(end + to_std(c10::TICK / 2)), None);
+    }
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Timing of the year lookups in `c10::epochs`, which should not depend on the distance from
+//! the Unix epoch.
+//!
+//! Run with `cargo bench -p c10time`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use c10::epochs;
+
+const ITERATIONS: u32 = 1_000_000;
+
+/// Reference year lookup walking forward one year at a time (from 1970 onward).
+fn linear_year_from_ticks(ticks: i64) -> i64 {
+    let mut guess = 1970;
+    while epochs::year_to_ticks(guess + 1) <= ticks {
+        guess += 1;
+    }
+    guess
+}
+
+/// Average time per call of `f` over `iterations` runs.
+fn time_per_call(iterations: u32, mut f: impl FnMut() -> i64) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    println!("{:>10} {:>14} {:>14}", "year", "closed-form", "linear");
+    for year in [1971, 2023, 3000, 10_000, 100_000] {
+        let ticks = epochs::year_to_ticks(year) + 12_345_678;
+
+        let closed = time_per_call(ITERATIONS, || epochs::year_from_ticks(black_box(ticks)));
+        let linear = time_per_call(ITERATIONS / 1000, || {
+            linear_year_from_ticks(black_box(ticks))
+        });
+
+        println!("{year:>10} {closed:>14?} {linear:>14?}");
+    }
+}
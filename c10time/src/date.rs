@@ -0,0 +1,416 @@
+//! Calendar dates: 36 decadays of ten days, then five or six named days to end the year.
+//!
+//! C10 years begin on the first of January, and leap years are those of the Gregorian calendar.
+//! The 360 days of the decadays leave five days over, or six in leap years. Rather than counting
+//! these as a short 37th decaday, as [`SystemTime::date_components`] does, a [`Date`] names them
+//! after the complementary days that closed the year of the French Republican calendar.
+
+use std::fmt;
+
+use crate::{epochs, Component, RangeError, SystemTime};
+
+/// Days in the decadays of a year.
+const DECADAY_DAYS: u64 = 360;
+
+/// A day in the C10 calendar.
+///
+/// Dates order chronologically and display as `YYYY DD.dd`, like the date of a [`SystemTime`],
+/// or as `YYYY` followed by the name of the day for the days after the 36th decaday:
+///
+/// ```
+/// let last = c10::Date::new(2024, 36, 10).unwrap();
+/// assert_eq!(last.to_string(), "2024 36.10");
+/// assert_eq!(last.succ().unwrap().to_string(), "2024 Virtue Day");
+/// assert_eq!(c10::Date::from_ordinal(2024, 366).unwrap().to_string(), "2024 Revolution Day");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i64,
+    // 1 to 365, or 366 in leap years
+    ordinal: u64,
+}
+
+/// The days that end the year, after its 36 decadays. The last comes only in leap years.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Epagomenal {
+    /// The first, the 361st day of the year.
+    Virtue = 1,
+    /// The second, the 362nd day of the year.
+    Talent,
+    /// The third, the 363rd day of the year.
+    Labour,
+    /// The fourth, the 364th day of the year.
+    Opinion,
+    /// The fifth, the 365th day of the year and the last of a common year.
+    Rewards,
+    /// The leap day, the 366th day of a leap year.
+    Revolution,
+}
+
+impl Epagomenal {
+    /// All the days, in order.
+    pub const ALL: [Epagomenal; 6] = [
+        Epagomenal::Virtue,
+        Epagomenal::Talent,
+        Epagomenal::Labour,
+        Epagomenal::Opinion,
+        Epagomenal::Rewards,
+        Epagomenal::Revolution,
+    ];
+
+    /// Returns the day's place among the days that end the year, from 1 to 6.
+    pub const fn number(self) -> u64 {
+        self as u64
+    }
+
+    /// Returns the day at place `number` among the days that end the year, if there is one.
+    pub fn from_number(number: u64) -> Option<Epagomenal> {
+        let index = usize::try_from(number.checked_sub(1)?).ok()?;
+        Epagomenal::ALL.get(index).copied()
+    }
+
+    /// Returns the day's name, such as `Virtue`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Epagomenal::Virtue => "Virtue",
+            Epagomenal::Talent => "Talent",
+            Epagomenal::Labour => "Labour",
+            Epagomenal::Opinion => "Opinion",
+            Epagomenal::Rewards => "Rewards",
+            Epagomenal::Revolution => "Revolution",
+        }
+    }
+}
+
+/// Formats as the day's name followed by `Day`, such as `Virtue Day`.
+impl fmt::Display for Epagomenal {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmter, "{} Day", self.name())
+    }
+}
+
+impl Date {
+    /// Creates the date of `day` (from 1 to 10) of `decaday` (from 1 to 36) in `year`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] naming the first component out of its valid range.
+    pub fn new(year: i64, decaday: u64, day: u64) -> Result<Date, RangeError> {
+        check_year(year)?;
+        if !(1..=36).contains(&decaday) {
+            return Err(Component::Decaday.out_of_range(decaday, 1, 36));
+        }
+        if !(1..=10).contains(&day) {
+            return Err(Component::Day.out_of_range(day, 1, 10));
+        }
+        Ok(Date {
+            year,
+            ordinal: (decaday - 1) * 10 + day,
+        })
+    }
+
+    /// Creates the date of the named day `day` at the end of `year`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] for the year if out of range, or for the day if it is
+    /// [`Epagomenal::Revolution`] and `year` is not a leap year.
+    pub fn from_epagomenal(year: i64, day: Epagomenal) -> Result<Date, RangeError> {
+        check_year(year)?;
+        let days = days_in_year(year) - DECADAY_DAYS;
+        if day.number() > days {
+            return Err(Component::Day.out_of_range(day.number(), 1, days));
+        }
+        Ok(Date {
+            year,
+            ordinal: DECADAY_DAYS + day.number(),
+        })
+    }
+
+    /// Creates the date of the `ordinal`th day of `year`, counting from 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] for the year if out of range, or for the day if
+    /// `ordinal` lies outside `1..=365` (or `1..=366` in leap years).
+    pub fn from_ordinal(year: i64, ordinal: u64) -> Result<Date, RangeError> {
+        check_year(year)?;
+        let days = days_in_year(year);
+        if !(1..=days).contains(&ordinal) {
+            return Err(Component::Day.out_of_range(ordinal, 1, days));
+        }
+        Ok(Date { year, ordinal })
+    }
+
+    /// Creates the date `days` days after 1970-01-01 (before it, if negative).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] for the year if the date lies in a year outside
+    /// the range of [`SystemTime`].
+    pub fn from_days_since_epoch(days: i64) -> Result<Date, RangeError> {
+        // a day either side of the range of years is out of range too, without overflowing
+        let (min, max) = year_range();
+        let days = days.clamp(epochs::year_to_days(min) - 1, epochs::year_to_days(max + 1));
+        let year = epochs::year_from_days(days);
+        Date::from_ordinal(year, (days - epochs::year_to_days(year)) as u64 + 1)
+    }
+
+    /// Returns the year.
+    pub const fn year(&self) -> i64 {
+        self.year
+    }
+
+    /// Returns the day of the year, counting from 1.
+    pub const fn ordinal(&self) -> u64 {
+        self.ordinal
+    }
+
+    /// Returns the decaday, from 1 to 36, or `None` for the days after the decadays.
+    pub const fn decaday(&self) -> Option<u64> {
+        match self.ordinal {
+            ..=DECADAY_DAYS => Some((self.ordinal - 1) / 10 + 1),
+            _ => None,
+        }
+    }
+
+    /// Returns the day of the decaday, from 1 to 10, or `None` for the days after the decadays.
+    pub const fn day(&self) -> Option<u64> {
+        match self.ordinal {
+            ..=DECADAY_DAYS => Some((self.ordinal - 1) % 10 + 1),
+            _ => None,
+        }
+    }
+
+    /// Returns which of the named days after the decadays this is, if it is one.
+    pub fn epagomenal(&self) -> Option<Epagomenal> {
+        Epagomenal::from_number(self.ordinal.checked_sub(DECADAY_DAYS)?)
+    }
+
+    /// Returns whether the date lies in a leap year, which ends with [`Epagomenal::Revolution`].
+    pub const fn is_leap_year(&self) -> bool {
+        epochs::is_leap_year(self.year)
+    }
+
+    /// Returns the number of days since 1970-01-01, negative for earlier dates.
+    pub const fn days_since_epoch(&self) -> i64 {
+        epochs::year_to_days(self.year) + self.ordinal as i64 - 1
+    }
+
+    /// Returns the next day, or `None` past the range of years.
+    pub fn succ(&self) -> Option<Date> {
+        match self.ordinal < days_in_year(self.year) {
+            true => Some(Date {
+                year: self.year,
+                ordinal: self.ordinal + 1,
+            }),
+            false => Date::from_ordinal(self.year + 1, 1).ok(),
+        }
+    }
+
+    /// Returns the previous day, or `None` past the range of years.
+    pub fn pred(&self) -> Option<Date> {
+        match self.ordinal > 1 {
+            true => Some(Date {
+                year: self.year,
+                ordinal: self.ordinal - 1,
+            }),
+            false => {
+                let year = self.year - 1;
+                Date::from_ordinal(year, days_in_year(year)).ok()
+            }
+        }
+    }
+
+    /// Returns the start of the day.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Timestamp`] if the time cannot be represented, as for the last days
+    /// of the latest year.
+    pub fn midnight(&self) -> Result<SystemTime, RangeError> {
+        let ticks = self.days_since_epoch().checked_mul(1_000_000);
+        SystemTime::from_ticks(ticks.ok_or(RangeError::Timestamp)?)
+    }
+}
+
+/// Formats as `YYYY DD.dd`, or as `YYYY` and the name of the day after the decadays, such as
+/// `2023 Virtue Day`.
+impl fmt::Display for Date {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        match (self.decaday(), self.day(), self.epagomenal()) {
+            (Some(decaday), Some(day), _) => write!(fmter, "{:4} {decaday:2}.{day:02}", self.year),
+            (_, _, Some(day)) => write!(fmter, "{:4} {day}", self.year),
+            _ => unreachable!("ordinal {} outside the year", self.ordinal),
+        }
+    }
+}
+
+impl SystemTime {
+    /// Returns the date of the day holding this time.
+    pub fn date(&self) -> Date {
+        let days = self.as_ticks().div_euclid(1_000_000);
+        let year = epochs::year_from_days(days);
+        Date {
+            year,
+            ordinal: (days - epochs::year_to_days(year)) as u64 + 1,
+        }
+    }
+}
+
+/// Returns the range of years holding representable times.
+fn year_range() -> (i64, i64) {
+    (
+        SystemTime::MIN.date_components().0,
+        SystemTime::MAX.date_components().0,
+    )
+}
+
+fn check_year(year: i64) -> Result<(), RangeError> {
+    let (min, max) = year_range();
+    match (min..=max).contains(&year) {
+        true => Ok(()),
+        false => Err(RangeError::Component {
+            component: Component::Year,
+            value: year,
+            min,
+            max,
+        }),
+    }
+}
+
+fn days_in_year(year: i64) -> u64 {
+    DECADAY_DAYS + 5 + u64::from(epochs::is_leap_year(year))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert_eq!(Date::new(2023, 1, 1).unwrap().ordinal(), 1);
+        assert_eq!(Date::new(2023, 36, 10).unwrap().ordinal(), 360);
+        assert_eq!(
+            Date::new(2023, 37, 1).unwrap_err().to_string(),
+            "decaday 37 is outside of the range 1..=36"
+        );
+        assert_eq!(
+            Date::new(2023, 5, 0).unwrap_err().to_string(),
+            "day 0 is outside of the range 1..=10"
+        );
+        assert!(matches!(
+            Date::new(i64::MAX, 1, 1),
+            Err(RangeError::Component {
+                component: Component::Year,
+                ..
+            })
+        ));
+
+        assert_eq!(
+            Date::from_epagomenal(2023, Epagomenal::Revolution)
+                .unwrap_err()
+                .to_string(),
+            "day 6 is outside of the range 1..=5"
+        );
+        assert_eq!(
+            Date::from_ordinal(2023, 366).unwrap_err().to_string(),
+            "day 366 is outside of the range 1..=365"
+        );
+        assert!(Date::from_ordinal(2023, 0).is_err());
+        assert!(Date::from_ordinal(2000, 366).is_ok());
+        assert!(Date::from_ordinal(1900, 366).is_err());
+    }
+
+    #[test]
+    fn days_after_the_decadays() {
+        let mut date = Date::new(2024, 36, 10).unwrap();
+        for day in Epagomenal::ALL {
+            date = date.succ().unwrap();
+            assert_eq!(date, Date::from_epagomenal(2024, day).unwrap());
+            assert_eq!(date.epagomenal(), Some(day));
+            assert_eq!((date.decaday(), date.day()), (None, None));
+            assert_eq!(Epagomenal::from_number(day.number()), Some(day));
+        }
+        assert_eq!(date.to_string(), "2024 Revolution Day");
+        assert_eq!(date.succ(), Date::new(2025, 1, 1).ok());
+
+        // common years skip the leap day
+        let rewards = Date::from_epagomenal(2023, Epagomenal::Rewards).unwrap();
+        assert_eq!(rewards.ordinal(), 365);
+        assert_eq!(rewards.succ(), Date::new(2024, 1, 1).ok());
+        assert_eq!(Date::new(2024, 1, 1).unwrap().pred(), Some(rewards));
+        assert_eq!(Epagomenal::from_number(0), None);
+        assert_eq!(Epagomenal::from_number(7), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Date::new(1970, 1, 1).unwrap().to_string(), "1970  1.01");
+        assert_eq!(Date::new(-44, 7, 5).unwrap().to_string(), " -44  7.05");
+        let talent = Date::from_epagomenal(2023, Epagomenal::Talent).unwrap();
+        assert_eq!(talent.to_string(), "2023 Talent Day");
+        assert_eq!(Epagomenal::Opinion.to_string(), "Opinion Day");
+    }
+
+    #[test]
+    fn ordinals_and_days_since_epoch() {
+        assert_eq!(Date::new(1970, 1, 1).unwrap().days_since_epoch(), 0);
+        assert_eq!(
+            Date::from_days_since_epoch(-1),
+            Date::from_epagomenal(1969, Epagomenal::Rewards)
+        );
+
+        let mut date = Date::new(1999, 1, 1).unwrap();
+        for days in date.days_since_epoch()..date.days_since_epoch() + 3 * 366 {
+            assert_eq!(date.days_since_epoch(), days);
+            assert_eq!(Date::from_days_since_epoch(days), Ok(date));
+            assert_eq!(Date::from_ordinal(date.year(), date.ordinal()), Ok(date));
+            if let (Some(decaday), Some(day)) = (date.decaday(), date.day()) {
+                assert_eq!(Date::new(date.year(), decaday, day), Ok(date));
+            }
+            let next = date.succ().unwrap();
+            assert!(next > date);
+            assert_eq!(next.pred(), Some(date));
+            date = next;
+        }
+        assert_eq!(date, Date::from_ordinal(2002, 3).unwrap());
+    }
+
+    #[test]
+    fn from_system_time() {
+        for ticks in [
+            0,
+            999_999,
+            1_000_000,
+            -1,
+            364 * 1_000_000,
+            1_234_567_890_123,
+        ] {
+            let time = SystemTime::from_ticks(ticks).unwrap();
+            let (year, decaday, day) = time.date_components();
+            let date = time.date();
+            assert_eq!(date.year(), year);
+            assert_eq!(date.ordinal(), (decaday - 1) * 10 + day);
+            assert_eq!(date.midnight().unwrap().date(), date);
+            assert!(date.midnight().unwrap() <= time);
+        }
+        let leap_day = SystemTime::from_c10_components(2000, 37, 6, 50, 0, 0).unwrap();
+        assert_eq!(leap_day.date().epagomenal(), Some(Epagomenal::Revolution));
+    }
+
+    #[test]
+    fn range_of_years() {
+        let first = SystemTime::MIN.date();
+        assert_eq!(first.midnight(), Ok(SystemTime::MIN));
+        assert_eq!(first.pred(), None);
+        assert!(Date::from_days_since_epoch(first.days_since_epoch() - 1).is_err());
+
+        let year = SystemTime::MAX.date().year();
+        let last = Date::from_ordinal(year, days_in_year(year)).unwrap();
+        assert_eq!(last.succ(), None);
+        assert_eq!(last.midnight(), Err(RangeError::Timestamp));
+        assert!(Date::from_days_since_epoch(i64::MAX).is_err());
+        assert!(Date::from_days_since_epoch(i64::MIN).is_err());
+    }
+}
@@ -1,52 +1,121 @@
 //! Module for handling conversions from Unix time and its associated epoch of New Years 1970.
+//!
+//! Years use astronomical numbering in the proleptic Gregorian calendar (year 0 is 1 BCE), and
+//! instants before the epoch are negative.
+//!
+//! Year lookups use closed-form proleptic Gregorian arithmetic over the 400-year civil cycle,
+//! so they run in constant time regardless of how far a timestamp lies from the epoch.
 
-#[allow(unused)]
 macro_rules! is_leap {
     ($year:expr) => {
         ($year % 4 == 0 && ($year % 100 != 0 || $year % 400 == 0)) as bool
     };
 }
 
+/// Nanoseconds in one tick (86.4 milliseconds).
+pub const NANOS_PER_TICK: u32 = 86_400_000;
+
+/// Days in one full 400-year cycle of the Gregorian calendar.
+const DAYS_PER_400Y: i64 = 400 * 365 + 97;
+/// Days in a 100-year span not containing a 400-divisible year.
+const DAYS_PER_100Y: i64 = 100 * 365 + 24;
+/// Days in a 4-year span containing one leap year.
+const DAYS_PER_4Y: i64 = 4 * 365 + 1;
+
+/// Days from January 1 of year 1 until January 1 of the given year (negative before year 1).
+const fn days_before_year(year: i64) -> i64 {
+    let y = year - 1;
+    y * 365 + y.div_euclid(4) - y.div_euclid(100) + y.div_euclid(400)
+}
+
+/// Days from January 1 of year 1 until the Unix epoch.
+const EPOCH_DAYS: i64 = days_before_year(1970);
+
+/// Returns true if the given year has 366 days.
+pub const fn is_leap_year(year: i64) -> bool {
+    is_leap!(year)
+}
+
 /// Days since the Unix epoch for a given year on January 1.
-pub const fn year_to_days(year: usize) -> usize {
-    let mut days = 0;
-    let mut year = year;
-    while year > 1970 {
-        days += 365;
-        if is_leap!(year) {
-            days += 1;
-        }
-        year -= 1;
-    }
-    days
+pub const fn year_to_days(year: i64) -> i64 {
+    days_before_year(year) - EPOCH_DAYS
 }
 
 /// Seconds after the Unix epoch for a given year.
-pub const fn year_to_seconds(year: usize) -> usize {
+pub const fn year_to_seconds(year: i64) -> i64 {
     year_to_days(year) * (24 * 60 * 60)
 }
 
 /// Ticks after the Unix epoch for a given year.
-pub const fn year_to_ticks(year: usize) -> u64 {
-    year_to_days(year) as u64 * (100 * 100 * 100)
+pub const fn year_to_ticks(year: i64) -> i64 {
+    year_to_days(year) * (100 * 100 * 100)
 }
 
-/// Returns the year to which a given Unix epoch time (seconds) belongs.
-pub const fn year_from_seconds(secs: usize) -> usize {
-    let mut guess = 1970;
-    while year_to_seconds(guess + 1) < secs {
-        guess += 1;
+/// Ticks since the Unix epoch for a given number of nanoseconds since the epoch, rounded toward
+/// the past.
+pub const fn nanos_to_ticks(nanos: i128) -> i128 {
+    // 1 tick = 0.0864 seconds ==> 625 ticks = 54 seconds
+    // split off whole 54-second spans first so that the multiplication cannot overflow
+    const NANOS_PER_SPAN: i128 = 54 * 1_000_000_000;
+    let spans = nanos.div_euclid(NANOS_PER_SPAN);
+    let rest = nanos.rem_euclid(NANOS_PER_SPAN);
+    spans * 625 + rest * 625 / NANOS_PER_SPAN
+}
+
+/// Nanoseconds since the Unix epoch for a given number of ticks since the epoch.
+///
+/// Every tick is exactly 86.4 milliseconds, so this conversion is exact.
+pub const fn ticks_to_nanos(ticks: i64) -> i128 {
+    ticks as i128 * NANOS_PER_TICK as i128
+}
+
+/// Nanoticks (billionths of a tick) for a number of nanoseconds less than one tick, rounded down.
+pub const fn nanos_to_nanoticks(nanos: u32) -> u32 {
+    // 1 tick = NANOS_PER_TICK nanoseconds ==> 625 nanoticks = 54 nanoseconds
+    (nanos as u64 * 625 / 54) as u32
+}
+
+/// Nanoseconds for a number of nanoticks less than one tick, rounded to the nearest nanosecond.
+///
+/// Nanoticks are finer than nanoseconds, so this exactly inverts [`nanos_to_nanoticks`].
+pub const fn nanoticks_to_nanos(nanoticks: u32) -> u32 {
+    ((nanoticks as u64 * 54 + 625 / 2) / 625) as u32
+}
+
+/// Returns the year to which a given number of days since the Unix epoch belongs.
+pub const fn year_from_days(days: i64) -> i64 {
+    // split days since 0001-01-01 into 400-, 100-, 4- and 1-year cycles
+    let days = days + EPOCH_DAYS;
+
+    let cycles_400 = days.div_euclid(DAYS_PER_400Y);
+    let mut days = days.rem_euclid(DAYS_PER_400Y);
+
+    // the last day of a 400-year cycle would otherwise count as a 5th century (or 4th year)
+    let mut cycles_100 = days / DAYS_PER_100Y;
+    if cycles_100 == 4 {
+        cycles_100 = 3;
+    }
+    days -= cycles_100 * DAYS_PER_100Y;
+
+    let cycles_4 = days / DAYS_PER_4Y;
+    days %= DAYS_PER_4Y;
+
+    let mut years = days / 365;
+    if years == 4 {
+        years = 3;
     }
-    guess
+
+    400 * cycles_400 + 100 * cycles_100 + 4 * cycles_4 + years + 1
+}
+
+/// Returns the year to which a given Unix epoch time (seconds) belongs.
+pub const fn year_from_seconds(secs: i64) -> i64 {
+    year_from_days(secs.div_euclid(24 * 60 * 60))
 }
 
 /// Returns the year to which a given c10 tick belongs.
-pub const fn year_from_ticks(ticks: u64) -> usize {
-    let mut guess = 1970;
-    while year_to_ticks(guess + 1) < ticks {
-        guess += 1;
-    }
-    guess
+pub const fn year_from_ticks(ticks: i64) -> i64 {
+    year_from_days(ticks.div_euclid(100 * 100 * 100))
 }
 
 #[cfg(test)]
@@ -68,4 +137,80 @@ mod tests {
         let year = year_from_seconds(1672531200 + 250000);
         assert_eq!(2023, year);
     }
+
+    #[test]
+    fn year_boundaries() {
+        for year in [
+            -1_000_000, -1, 0, 1, 1900, 1969, 1970, 1971, 2000, 2023, 2400, 1_000_000,
+        ] {
+            let ticks = year_to_ticks(year);
+            assert_eq!(year_from_ticks(ticks), year);
+            assert_eq!(year_from_ticks(year_to_ticks(year + 1) - 1), year);
+        }
+        assert_eq!(year_from_seconds(1672531200 - 1), 2022);
+        assert_eq!(year_from_seconds(1672531200), 2023);
+    }
+
+    #[test]
+    fn nanos_to_ticks_rounding() {
+        assert_eq!(nanos_to_ticks(0), 0);
+        assert_eq!(nanos_to_ticks(86_399_999), 0);
+        assert_eq!(nanos_to_ticks(86_400_000), 1);
+        assert_eq!(nanos_to_ticks(-1), -1);
+        assert_eq!(nanos_to_ticks(-86_400_000), -1);
+        assert_eq!(nanos_to_ticks(-86_400_001), -2);
+        assert_eq!(
+            nanos_to_ticks(1672531200 * 1_000_000_000),
+            year_to_ticks(2023) as i128
+        );
+        assert!(nanos_to_ticks(i128::MIN) < 0);
+    }
+
+    #[test]
+    fn ticks_nanos_round_trip() {
+        for ticks in [i64::MIN, -625, -1, 0, 1, 625, 54, i64::MAX] {
+            assert_eq!(nanos_to_ticks(ticks_to_nanos(ticks)), ticks as i128);
+        }
+        assert_eq!(ticks_to_nanos(625), 54_000_000_000);
+    }
+
+    #[test]
+    fn nanoticks_round_trip() {
+        for nanos in (0..86_400_000)
+            .step_by(9_973)
+            .chain([1, 53, 54, 86_399_999])
+        {
+            assert_eq!(nanoticks_to_nanos(nanos_to_nanoticks(nanos)), nanos);
+        }
+        assert_eq!(nanos_to_nanoticks(54), 625);
+        assert_eq!(nanos_to_nanoticks(86_399_999), 999_999_988);
+        assert_eq!(nanoticks_to_nanos(999_999_999), 86_400_000);
+    }
+
+    #[test]
+    fn before_epoch() {
+        assert_eq!(year_to_days(1969), -365);
+        assert_eq!(year_to_seconds(1900), -2208988800);
+        assert_eq!(year_to_days(1), -719162);
+        assert_eq!(year_to_days(0), -719162 - 366);
+        assert_eq!(year_from_days(-1), 1969);
+        assert_eq!(year_from_days(-365), 1969);
+        assert_eq!(year_from_days(-366), 1968);
+        assert_eq!(year_from_seconds(-1), 1969);
+        assert_eq!(year_from_ticks(-1), 1969);
+        assert_eq!(year_from_days(-719162), 1);
+        assert_eq!(year_from_days(-719163), 0);
+        assert_eq!(year_from_days(-719162 - 367), -1);
+    }
+
+    #[test]
+    fn matches_linear_scan() {
+        let mut days = year_to_days(-800);
+        for year in -800..3000 {
+            assert_eq!(year_to_days(year), days, "start of {year}");
+            assert_eq!(year_from_days(days), year, "first day of {year}");
+            days += if is_leap!(year) { 366 } else { 365 };
+            assert_eq!(year_from_days(days - 1), year, "last day of {year}");
+        }
+    }
 }
@@ -0,0 +1,504 @@
+//! Custom layouts for C10 dates and times, in the style of `strftime`.
+//!
+//! A format string is literal text interspersed with specifiers introduced by `%`:
+//!
+//! | Specifier | Meaning                                   | Default padding |
+//! |-----------|-------------------------------------------|-----------------|
+//! | `%Y`      | year (negative before 1 BCE)              | spaces, 4 wide  |
+//! | `%D`      | decaday, 1–37                             | spaces, 2 wide  |
+//! | `%d`      | day of the decaday, 1–10                  | zeros, 2 wide   |
+//! | `%j`      | day of the year, 1–366                    | zeros, 3 wide   |
+//! | `%I`      | interval, 0–99                            | zeros, 2 wide   |
+//! | `%C`      | centival, 0–99                            | zeros, 2 wide   |
+//! | `%T`      | tick, 0–99                                | zeros, 2 wide   |
+//! | `%f`      | sub-tick digits, truncated                | 9 digits        |
+//! | `%%`      | a literal `%`                             |                 |
+//!
+//! Between the `%` and the specifier letter, an optional flag overrides the padding — `-` for
+//! none, `_` for spaces, `0` for zeros — and an optional decimal width of up to 64 overrides the
+//! width. For `%f` the width is instead the number of digits shown, up to 9. For example, `%-d`
+//! renders the 7th day as `7` and `%3f` renders three sub-tick digits.
+//!
+//! Unrecognized specifiers, including those wider than 64, are rendered as written. The layout
+//! used by `Display` for [`SystemTime`] is `%Y %D.%d %I:%C:%T`.
+
+use std::fmt;
+
+use crate::parse::{Cursor, ParseError, ParseErrorKind};
+use crate::{epochs, Component, Duration, RangeError, SystemTime};
+
+/// The widest a field may be padded, so that a format string cannot demand unbounded padding.
+const MAX_WIDTH: usize = 64;
+
+/// How a numeric field is padded to its width.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Pad {
+    None,
+    Space,
+    Zero,
+}
+
+/// The field a specifier refers to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Field {
+    Year,
+    Decaday,
+    Day,
+    DayOfYear,
+    Interval,
+    Centival,
+    Tick,
+    Subtick,
+}
+
+impl Field {
+    fn from_letter(letter: char) -> Option<Field> {
+        Some(match letter {
+            'Y' => Field::Year,
+            'D' => Field::Decaday,
+            'd' => Field::Day,
+            'j' => Field::DayOfYear,
+            'I' => Field::Interval,
+            'C' => Field::Centival,
+            'T' => Field::Tick,
+            'f' => Field::Subtick,
+            _ => return None,
+        })
+    }
+
+    fn default_padding(self) -> (Pad, usize) {
+        match self {
+            Field::Year => (Pad::Space, 4),
+            Field::Decaday => (Pad::Space, 2),
+            Field::DayOfYear => (Pad::Zero, 3),
+            Field::Subtick => (Pad::Zero, 9),
+            _ => (Pad::Zero, 2),
+        }
+    }
+
+    /// The component blamed when this field fails to parse.
+    fn component(self) -> Component {
+        match self {
+            Field::Year => Component::Year,
+            Field::Decaday => Component::Decaday,
+            Field::Day | Field::DayOfYear => Component::Day,
+            Field::Interval => Component::Interval,
+            Field::Centival => Component::Centival,
+            Field::Tick | Field::Subtick => Component::Tick,
+        }
+    }
+}
+
+/// One piece of a format string.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Item<'a> {
+    /// Text to reproduce as-is, including unrecognized specifiers.
+    Literal(&'a str),
+    /// A recognized specifier, with its padding resolved.
+    Spec {
+        field: Field,
+        pad: Pad,
+        width: usize,
+    },
+    /// A specifier which could not be interpreted, at the given byte offset.
+    Unknown(&'a str, usize),
+}
+
+/// Splits a format string into literal text and specifiers.
+struct Items<'a> {
+    format: &'a str,
+    pos: usize,
+}
+
+impl<'a> Items<'a> {
+    fn new(format: &'a str) -> Items<'a> {
+        Items { format, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for Items<'a> {
+    type Item = Item<'a>;
+
+    fn next(&mut self) -> Option<Item<'a>> {
+        let rest = &self.format[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let start = self.pos;
+        if !rest.starts_with('%') {
+            let len = rest.find('%').unwrap_or(rest.len());
+            self.pos += len;
+            return Some(Item::Literal(&rest[..len]));
+        }
+        if rest.starts_with("%%") {
+            self.pos += 2;
+            return Some(Item::Literal("%"));
+        }
+
+        // %[flag][width]letter
+        let mut chars = rest.char_indices().skip(1).peekable();
+        let flag = match chars.peek() {
+            Some((_, '-')) => Some(Pad::None),
+            Some((_, '_')) => Some(Pad::Space),
+            Some((_, '0')) => Some(Pad::Zero),
+            _ => None,
+        };
+        if flag.is_some() {
+            chars.next();
+        }
+        let mut width = None;
+        while let Some(&(_, c)) = chars.peek() {
+            let Some(digit) = c.to_digit(10) else { break };
+            width = Some(
+                width
+                    .unwrap_or(0_usize)
+                    .saturating_mul(10)
+                    .saturating_add(digit as usize),
+            );
+            chars.next();
+        }
+        let (end, field) = match chars.next() {
+            Some((at, letter)) => (at + letter.len_utf8(), Field::from_letter(letter)),
+            None => (rest.len(), None),
+        };
+        self.pos += end;
+
+        Some(match field {
+            Some(field) if width.unwrap_or(0) <= MAX_WIDTH => {
+                let (pad, default_width) = field.default_padding();
+                Item::Spec {
+                    field,
+                    pad: flag.unwrap_or(pad),
+                    width: width.unwrap_or(default_width),
+                }
+            }
+            _ => Item::Unknown(&rest[..end], start),
+        })
+    }
+}
+
+/// A [`SystemTime`] rendered in a custom layout; see the [module docs](self) for the syntax.
+///
+/// Created by [`SystemTime::format`]. The format string is interpreted each time the value is
+/// displayed.
+#[derive(Debug, Copy, Clone)]
+pub struct Formatted<'a> {
+    time: SystemTime,
+    format: &'a str,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        let (year, decaday, day) = self.time.date_components();
+        let (ints, cents, ticks) = self.time.time_components();
+
+        for item in Items::new(self.format) {
+            let (field, pad, width) = match item {
+                Item::Literal(text) | Item::Unknown(text, _) => {
+                    fmter.write_str(text)?;
+                    continue;
+                }
+                Item::Spec { field, pad, width } => (field, pad, width),
+            };
+            let value = match field {
+                Field::Year => year,
+                Field::Decaday => decaday as i64,
+                Field::Day => day as i64,
+                Field::DayOfYear => ((decaday - 1) * 10 + day) as i64,
+                Field::Interval => ints as i64,
+                Field::Centival => cents as i64,
+                Field::Tick => ticks as i64,
+                Field::Subtick => {
+                    let digits = width.clamp(1, 9);
+                    let fraction = self.time.subtick_nanoticks() / 10_u32.pow(9 - digits as u32);
+                    write!(fmter, "{fraction:0digits$}")?;
+                    continue;
+                }
+            };
+            match pad {
+                Pad::None => write!(fmter, "{value}")?,
+                Pad::Space => write!(fmter, "{value:width$}")?,
+                Pad::Zero => write!(fmter, "{value:0width$}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SystemTime {
+    /// Returns a value which displays this time in a custom layout, such as `"%Y-%D-%d"`.
+    ///
+    /// See the [`format`](crate::format) module for the supported specifiers.
+    pub fn format<'a>(&self, format: &'a str) -> Formatted<'a> {
+        Formatted {
+            time: *self,
+            format,
+        }
+    }
+
+    /// Parses a time laid out as described by a format string, the inverse of
+    /// [`SystemTime::format`].
+    ///
+    /// Literal text must match exactly, except that whitespace in the format matches any run of
+    /// whitespace (including none). Padded fields accept up to their width in digits, preceded by
+    /// spaces if space-padded; unpadded fields accept any number of digits. The year is required,
+    /// while any other field that is absent takes its first value.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] locating the first part of `input` which does not match. An
+    /// unrecognized specifier yields [`ParseErrorKind::InvalidFormat`], positioned within
+    /// `format` rather than `input`.
+    pub fn parse_format(input: &str, format: &str) -> Result<SystemTime, ParseError> {
+        let mut cursor = Cursor::new(input);
+        let mut year = None;
+        let mut decaday = None;
+        let mut day = None;
+        let mut day_of_year = None;
+        let mut time = [0_u64; 3];
+        let mut nanoticks = 0;
+        // where each component was found, indexed by `Component as usize`
+        let mut positions = [0_usize; 6];
+
+        for item in Items::new(format) {
+            let (field, pad, width) = match item {
+                Item::Unknown(_, at) => {
+                    return Err(ParseError::new(ParseErrorKind::InvalidFormat, at));
+                }
+                Item::Literal(text) => {
+                    for c in text.chars() {
+                        if c.is_whitespace() {
+                            cursor.skip_whitespace();
+                        } else {
+                            cursor.expect(c)?;
+                        }
+                    }
+                    continue;
+                }
+                Item::Spec { field, pad, width } => (field, pad, width),
+            };
+
+            if field == Field::Subtick {
+                let start = cursor.pos();
+                let digits = cursor.digits(width.clamp(1, 9));
+                if digits.is_empty() {
+                    return Err(ParseError::new(ParseErrorKind::InvalidSubtick, start));
+                }
+                let scale = 10_u32.pow(9 - digits.len() as u32);
+                nanoticks = digits.parse::<u32>().unwrap_or(0) * scale;
+                continue;
+            }
+
+            // padding spaces count toward the field's width
+            let padding = match pad {
+                Pad::Space => cursor.skip_whitespace(),
+                _ => 0,
+            };
+            let at = cursor.pos();
+            let component = field.component();
+            positions[component as usize] = at;
+            let max_digits = match (field, pad) {
+                (Field::Year, _) | (_, Pad::None) => 20,
+                _ => width.saturating_sub(padding).max(1),
+            };
+            if field == Field::Year {
+                year = Some(cursor.signed(component, max_digits)?);
+                continue;
+            }
+            let value = cursor.number(component, max_digits)?;
+            match field {
+                Field::Decaday => decaday = Some(value),
+                Field::Day => day = Some(value),
+                Field::DayOfYear => day_of_year = Some(value),
+                Field::Interval => time[0] = value,
+                Field::Centival => time[1] = value,
+                Field::Tick => time[2] = value,
+                Field::Year | Field::Subtick => unreachable!(),
+            }
+        }
+        cursor.finish()?;
+
+        let Some(year) = year else {
+            let missing = ParseErrorKind::Invalid(Component::Year);
+            return Err(ParseError::new(missing, cursor.pos()));
+        };
+        let out_of_range = |err: RangeError| {
+            let position = match err {
+                RangeError::Component { component, .. } => positions[component as usize],
+                RangeError::Timestamp => 0,
+            };
+            ParseError::new(ParseErrorKind::OutOfRange(err), position)
+        };
+
+        // a day of the year stands in for the decaday and day
+        let (decaday, day) = match day_of_year {
+            Some(ordinal) => {
+                SystemTime::check_year(year).map_err(out_of_range)?;
+                let days = 365 + i64::from(epochs::is_leap_year(year));
+                if ordinal == 0 || ordinal > days as u64 {
+                    return Err(out_of_range(RangeError::Component {
+                        component: Component::Day,
+                        value: ordinal.try_into().unwrap_or(i64::MAX),
+                        min: 1,
+                        max: days,
+                    }));
+                }
+                ((ordinal - 1) / 10 + 1, (ordinal - 1) % 10 + 1)
+            }
+            None => (decaday.unwrap_or(1), day.unwrap_or(1)),
+        };
+
+        let [ints, cents, ticks] = time;
+        let time = SystemTime::from_c10_components(year, decaday, day, ints, cents, ticks)
+            .map_err(out_of_range)?;
+        time.checked_add(Duration::from_nanoticks(nanoticks as u128))
+            .ok_or_else(|| out_of_range(RangeError::Timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TICK;
+
+    fn sample() -> SystemTime {
+        SystemTime::from_c10_components(2023, 4, 7, 5, 34, 6).unwrap() + TICK / 8
+    }
+
+    #[test]
+    fn display_layout() {
+        let time = sample();
+        assert_eq!(
+            time.format("%Y %D.%d %I:%C:%T").to_string(),
+            time.to_string()
+        );
+        let before = SystemTime::UNIX_EPOCH - TICK;
+        assert_eq!(
+            before.format("%Y %D.%d %I:%C:%T").to_string(),
+            before.to_string()
+        );
+    }
+
+    #[test]
+    fn specifiers() {
+        let time = sample();
+        assert_eq!(time.format("%j").to_string(), "037");
+        assert_eq!(time.format("%-D/%-d %-I").to_string(), "4/7 5");
+        assert_eq!(time.format("%0D|%_d|%_4I").to_string(), "04| 7|   5");
+        assert_eq!(
+            time.format("%T.%3f|%f|%1f").to_string(),
+            "06.125|125000000|1"
+        );
+        assert_eq!(
+            time.format("%6Y|%-Y|%06Y").to_string(),
+            "  2023|2023|002023"
+        );
+        assert_eq!(time.format("100%% at %I").to_string(), "100% at 05");
+        assert_eq!(time.format("%q %").to_string(), "%q %");
+        assert_eq!(time.format("%64Y").to_string().len(), 64);
+        assert_eq!(time.format("%65Y|%70000I").to_string(), "%65Y|%70000I");
+        assert_eq!(
+            time.format("%99999999999999999999999T").to_string(),
+            "%99999999999999999999999T"
+        );
+        assert_eq!(time.format("").to_string(), "");
+        assert_eq!(
+            SystemTime::from_c10_components(-44, 1, 1, 0, 0, 0)
+                .unwrap()
+                .format("%Y|%05Y")
+                .to_string(),
+            " -44|-0044"
+        );
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let time = sample();
+        for format in [
+            "%Y %D.%d %I:%C:%T.%f",
+            "%-Y-%-D-%-d %-I.%-C.%-T.%f",
+            "%D%d%I%C%T%f %Y",
+            "day %j of %Y, %I:%C:%T.%3f",
+        ] {
+            let rendered = time.format(format).to_string();
+            let parsed = SystemTime::parse_format(&rendered, format).unwrap();
+            assert_eq!(parsed, time, "{format:?} rendered as {rendered:?}");
+        }
+    }
+
+    #[test]
+    fn parse_defaults() {
+        assert_eq!(
+            SystemTime::parse_format("2023", "%Y"),
+            SystemTime::from_c10_components(2023, 1, 1, 0, 0, 0).map_err(|_| unreachable!())
+        );
+        assert_eq!(
+            SystemTime::parse_format("2023 d361", "%Y  d%j"),
+            SystemTime::from_c10_components(2023, 37, 1, 0, 0, 0).map_err(|_| unreachable!())
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |input, format| {
+            let err = SystemTime::parse_format(input, format).unwrap_err();
+            (err.kind(), err.position())
+        };
+        assert_eq!(
+            err("12", "%I"),
+            (ParseErrorKind::Invalid(Component::Year), 2)
+        );
+        assert_eq!(err("2023", "%Y %q"), (ParseErrorKind::InvalidFormat, 3));
+        assert_eq!(err("2023", "%70000Y"), (ParseErrorKind::InvalidFormat, 0));
+        assert_eq!(err("2023/1", "%Y-%D"), (ParseErrorKind::Separator('-'), 4));
+        assert_eq!(
+            err("2023-x", "%Y-%D"),
+            (ParseErrorKind::Invalid(Component::Decaday), 5)
+        );
+        assert_eq!(err("2023 1 2", "%Y %D"), (ParseErrorKind::Trailing, 6));
+        assert_eq!(err("2023.", "%Y.%f"), (ParseErrorKind::InvalidSubtick, 5));
+        assert_eq!(
+            err("2023 37 6", "%Y %D %d"),
+            (
+                ParseErrorKind::OutOfRange(RangeError::Component {
+                    component: Component::Day,
+                    value: 6,
+                    min: 1,
+                    max: 5
+                }),
+                8
+            )
+        );
+        // the year is checked before the length of the year is worked out
+        let (max_year, _, _) = SystemTime::MAX.date_components();
+        for year in [i64::MAX, 99_999_999_999_999_999, max_year + 1] {
+            let input = format!("{year} 1");
+            let error = SystemTime::parse_format(&input, "%Y %j").unwrap_err();
+            let (kind, at) = (error.kind(), error.position());
+            assert!(
+                matches!(
+                    kind,
+                    ParseErrorKind::OutOfRange(RangeError::Component {
+                        component: Component::Year,
+                        ..
+                    })
+                ),
+                "{year}: {kind:?}"
+            );
+            assert_eq!(at, 0);
+        }
+        assert_eq!(
+            err("2023 366", "%Y %j"),
+            (
+                ParseErrorKind::OutOfRange(RangeError::Component {
+                    component: Component::Day,
+                    value: 366,
+                    min: 1,
+                    max: 365
+                }),
+                5
+            )
+        );
+    }
+}
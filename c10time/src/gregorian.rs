@@ -0,0 +1,176 @@
+//! Conversion to the conventional Gregorian calendar and 24-hour clock.
+//!
+//! Dates use the same proleptic Gregorian calendar with astronomical year numbering as the rest
+//! of the crate, and times of day follow Unix time, so every day has exactly 86,400 seconds.
+
+use std::fmt;
+
+use crate::{epochs, SystemTime};
+
+/// Days before the first of each month in a common year.
+pub(crate) const DAYS_BEFORE_MONTH: [u32; 12] =
+    [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const SECONDS_PER_DAY: i128 = 86_400;
+
+/// A Gregorian date and time of day, as read on a conventional clock.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    /// Year, with year 0 being 1 BCE.
+    pub year: i64,
+    /// Month of the year, 1–12.
+    pub month: u32,
+    /// Day of the month, 1–31.
+    pub day: u32,
+    /// Hour of the day, 0–23.
+    pub hour: u32,
+    /// Minute of the hour, 0–59.
+    pub minute: u32,
+    /// Second of the minute, 0–59.
+    pub second: u32,
+    /// Nanoseconds past the second.
+    pub nanosecond: u32,
+}
+
+impl SystemTime {
+    /// Returns the Gregorian date and time of day of this instant, to the nearest nanosecond.
+    ///
+    /// The result is in UTC, or in local time for a time shifted by [`SystemTime::to_local`].
+    pub fn to_gregorian(&self) -> DateTime {
+        let nanos = self.unix_nanos();
+        let secs = nanos.div_euclid(NANOS_PER_SECOND);
+        let days = secs.div_euclid(SECONDS_PER_DAY) as i64;
+        let secs_of_day = secs.rem_euclid(SECONDS_PER_DAY) as u32;
+
+        let year = epochs::year_from_days(days);
+        let day_of_year = (days - epochs::year_to_days(year)) as u32;
+        let leap = epochs::is_leap_year(year) as u32;
+
+        // find the month by its first day, allowing for February 29 in leap years
+        let month = (1..12)
+            .rev()
+            .find(|&m| day_of_year >= DAYS_BEFORE_MONTH[m] + leap * (m >= 2) as u32)
+            .unwrap_or(0);
+        let first = DAYS_BEFORE_MONTH[month] + leap * (month >= 2) as u32;
+
+        DateTime {
+            year,
+            month: month as u32 + 1,
+            day: day_of_year - first + 1,
+            hour: secs_of_day / 3_600,
+            minute: secs_of_day / 60 % 60,
+            second: secs_of_day % 60,
+            nanosecond: nanos.rem_euclid(NANOS_PER_SECOND) as u32,
+        }
+    }
+}
+
+/// Returns the instant of a Gregorian date and time in UTC, for writing tests in familiar terms.
+#[cfg(test)]
+pub(crate) fn utc(
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+) -> SystemTime {
+    let leap = epochs::is_leap_year(year) && month > 2;
+    let first = i64::from(DAYS_BEFORE_MONTH[month as usize - 1]) + i64::from(leap);
+    let days = epochs::year_to_days(year) + first + i64::from(day) - 1;
+    let secs = days * SECONDS_PER_DAY as i64 + i64::from(hour * 3_600 + minute * 60 + second);
+    SystemTime::from_unix_seconds(secs).unwrap()
+}
+
+/// Formats as `YYYY-MM-DD HH:MM:SS`, with fractional-second digits following when a precision is
+/// given.
+impl fmt::Display for DateTime {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmter,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        match fmter.precision() {
+            None | Some(0) => Ok(()),
+            Some(precision) => {
+                let digits = precision.min(9);
+                let fraction = self.nanosecond / 10_u32.pow(9 - digits as u32);
+                write!(fmter, ".{fraction:0digits$}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gregorian(secs: i64) -> String {
+        SystemTime::from_unix_seconds(secs)
+            .unwrap()
+            .to_gregorian()
+            .to_string()
+    }
+
+    // civil date from days since the epoch, after Howard Hinnant's `civil_from_days`
+    fn civil_from_days(days: i64) -> (i64, u32, u32) {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + (month <= 2) as i64;
+        (year, month, day)
+    }
+
+    #[test]
+    fn known_dates() {
+        assert_eq!(gregorian(0), "1970-01-01 00:00:00");
+        assert_eq!(gregorian(-1), "1969-12-31 23:59:59");
+        assert_eq!(gregorian(951_827_696), "2000-02-29 12:34:56");
+        assert_eq!(gregorian(951_868_800), "2000-03-01 00:00:00");
+        assert_eq!(gregorian(1_700_000_000), "2023-11-14 22:13:20");
+        assert_eq!(gregorian(4_102_444_799), "2099-12-31 23:59:59");
+        assert_eq!(
+            gregorian(epochs::year_to_seconds(0) - 1),
+            "-001-12-31 23:59:59"
+        );
+    }
+
+    #[test]
+    fn fractions() {
+        let time = SystemTime::from_unix_nanos(1_500_000_001).unwrap();
+        let date = time.to_gregorian();
+        assert_eq!(date.second, 1);
+        assert_eq!(date.nanosecond, 500_000_001);
+        assert_eq!(format!("{date:.3}"), "1970-01-01 00:00:01.500");
+        assert_eq!(format!("{date:.12}"), "1970-01-01 00:00:01.500000001");
+
+        // ticks are whole numbers of milliseconds apart
+        let tick = SystemTime::from_ticks(1).unwrap().to_gregorian();
+        assert_eq!((tick.second, tick.nanosecond), (0, 86_400_000));
+
+        let before = SystemTime::from_unix_nanos(-1).unwrap().to_gregorian();
+        assert_eq!((before.second, before.nanosecond), (59, 999_999_999));
+    }
+
+    #[test]
+    fn matches_civil_from_days() {
+        for days in (-800_000..800_000).step_by(97) {
+            let date = SystemTime::from_unix_seconds(days * 86_400 + 45_296)
+                .unwrap()
+                .to_gregorian();
+            assert_eq!(
+                (date.year, date.month, date.day),
+                civil_from_days(days),
+                "{days}"
+            );
+            assert_eq!((date.hour, date.minute, date.second), (12, 34, 56));
+        }
+    }
+}
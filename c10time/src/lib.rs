@@ -10,8 +10,20 @@
 mod tests;
 
 use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
 
+pub mod date;
 pub mod epochs;
+pub mod format;
+pub mod gregorian;
+pub mod offset;
+pub mod parse;
+pub mod solar;
+pub mod timescale;
+pub mod zone;
+
+pub use date::{Date, Epagomenal};
 
 extern crate libc;
 use libc::{clock_gettime, timespec, CLOCK_REALTIME};
@@ -22,13 +34,39 @@ pub const INTERVAL: Duration = Duration::new(1, 0, 0);
 pub const DAY: Duration = Duration::new(100, 0, 0);
 pub const DECADAY: Duration = Duration::new(10 * 100, 0, 0);
 
+/// Nanoticks (billionths of a tick) in one tick.
+const NANOTICKS_PER_TICK: u32 = 1_000_000_000;
+
+/// Converts whole seconds, such as a zone's offset from UTC, to nanoticks, truncating toward
+/// zero.
+const fn seconds_to_nanoticks(secs: i64) -> i128 {
+    // 1 second = 625/54 ticks
+    secs as i128 * 625 * NANOTICKS_PER_TICK as i128 / 54
+}
+
 /// Representation for a unit of duration in C10 time.
+///
+/// Durations are kept to the nanotick, a billionth of a tick (86.4 femtoseconds), which is fine
+/// enough to hold any nanosecond-precision duration exactly.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Duration {
     ticks: u64,
+    nanoticks: u32,
 }
 
 impl Duration {
+    /// A duration of zero time.
+    pub const ZERO: Duration = Duration {
+        ticks: 0,
+        nanoticks: 0,
+    };
+
+    /// The maximum representable duration.
+    pub const MAX: Duration = Duration {
+        ticks: u64::MAX,
+        nanoticks: NANOTICKS_PER_TICK - 1,
+    };
+
     /// Creates a [`Duration`] from constituent components: intervals, centivals, ticks.
     ///
     /// # Panics
@@ -57,7 +95,63 @@ impl Duration {
             None => panic!("centivals+ticks overflow in Duration::new"),
         };
 
-        Duration { ticks }
+        Duration::from_ticks(ticks)
+    }
+
+    /// Creates a [`Duration`] from a raw number of ticks.
+    pub const fn from_ticks(ticks: u64) -> Duration {
+        Duration {
+            ticks,
+            nanoticks: 0,
+        }
+    }
+
+    /// Creates a [`Duration`] from a number of nanoticks (billionths of a tick).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result would be longer than [`Duration::MAX`].
+    pub const fn from_nanoticks(nanoticks: u128) -> Duration {
+        match Duration::checked_from_nanoticks(nanoticks) {
+            Some(duration) => duration,
+            None => panic!("nanoticks overflow in Duration::from_nanoticks"),
+        }
+    }
+
+    const fn checked_from_nanoticks(nanoticks: u128) -> Option<Duration> {
+        let ticks = nanoticks / NANOTICKS_PER_TICK as u128;
+        if ticks > u64::MAX as u128 {
+            return None;
+        }
+        Some(Duration {
+            ticks: ticks as u64,
+            nanoticks: (nanoticks % NANOTICKS_PER_TICK as u128) as u32,
+        })
+    }
+
+    /// Returns the total number of whole ticks in this duration.
+    pub const fn as_ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns the total number of nanoticks in this duration.
+    pub const fn as_nanoticks(&self) -> u128 {
+        self.ticks as u128 * NANOTICKS_PER_TICK as u128 + self.nanoticks as u128
+    }
+
+    /// Returns the fractional part of this duration's last tick, in nanoticks.
+    pub const fn subtick_nanoticks(&self) -> u32 {
+        self.nanoticks
+    }
+
+    /// Returns the fractional part of this duration's last tick, in whole microticks.
+    pub const fn subtick_microticks(&self) -> u32 {
+        self.nanoticks / 1000
+    }
+
+    /// Returns true if this duration spans no time.
+    pub const fn is_zero(&self) -> bool {
+        self.ticks == 0 && self.nanoticks == 0
     }
 
     /// Extracts the interval, centival, and tick components.
@@ -67,34 +161,337 @@ impl Duration {
         let ints = (self.ticks / (100 * 100)) % 100;
         (ints, cents, ticks)
     }
+
+    /// Checked addition. Returns `None` if the result would overflow.
+    pub const fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        Duration::checked_from_nanoticks(self.as_nanoticks() + rhs.as_nanoticks())
+    }
+
+    /// Checked subtraction. Returns `None` if `rhs` is longer than `self`.
+    pub const fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        match self.as_nanoticks().checked_sub(rhs.as_nanoticks()) {
+            Some(nanoticks) => Duration::checked_from_nanoticks(nanoticks),
+            None => None,
+        }
+    }
+
+    /// Checked multiplication by a scalar. Returns `None` if the result would overflow.
+    pub const fn checked_mul(self, rhs: u64) -> Option<Duration> {
+        match self.as_nanoticks().checked_mul(rhs as u128) {
+            Some(nanoticks) => Duration::checked_from_nanoticks(nanoticks),
+            None => None,
+        }
+    }
+
+    /// Checked division by a scalar. Returns `None` if `rhs` is zero.
+    ///
+    /// The result is truncated to a whole number of nanoticks.
+    pub const fn checked_div(self, rhs: u64) -> Option<Duration> {
+        match self.as_nanoticks().checked_div(rhs as u128) {
+            Some(nanoticks) => Duration::checked_from_nanoticks(nanoticks),
+            None => None,
+        }
+    }
+
+    /// Checked remainder. Returns `None` if `rhs` is zero.
+    pub const fn checked_rem(self, rhs: Duration) -> Option<Duration> {
+        match self.as_nanoticks().checked_rem(rhs.as_nanoticks()) {
+            Some(nanoticks) => Duration::checked_from_nanoticks(nanoticks),
+            None => None,
+        }
+    }
+
+    /// Saturating addition. Returns [`Duration::MAX`] if the result would overflow.
+    pub const fn saturating_add(self, rhs: Duration) -> Duration {
+        match self.checked_add(rhs) {
+            Some(duration) => duration,
+            None => Duration::MAX,
+        }
+    }
+
+    /// Saturating subtraction. Returns [`Duration::ZERO`] if `rhs` is longer than `self`.
+    pub const fn saturating_sub(self, rhs: Duration) -> Duration {
+        match self.checked_sub(rhs) {
+            Some(duration) => duration,
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Saturating multiplication by a scalar. Returns [`Duration::MAX`] if the result would
+    /// overflow.
+    pub const fn saturating_mul(self, rhs: u64) -> Duration {
+        match self.checked_mul(rhs) {
+            Some(duration) => duration,
+            None => Duration::MAX,
+        }
+    }
+
+    /// Wrapping addition, modulo [`Duration::MAX`] + 1 nanotick.
+    pub const fn wrapping_add(self, rhs: Duration) -> Duration {
+        let nanoticks = self.nanoticks + rhs.nanoticks;
+        let carry = (nanoticks / NANOTICKS_PER_TICK) as u64;
+        Duration {
+            ticks: self.ticks.wrapping_add(rhs.ticks).wrapping_add(carry),
+            nanoticks: nanoticks % NANOTICKS_PER_TICK,
+        }
+    }
+
+    /// Wrapping subtraction, modulo [`Duration::MAX`] + 1 nanotick.
+    pub const fn wrapping_sub(self, rhs: Duration) -> Duration {
+        let (nanoticks, borrow) = if self.nanoticks >= rhs.nanoticks {
+            (self.nanoticks - rhs.nanoticks, 0)
+        } else {
+            (self.nanoticks + NANOTICKS_PER_TICK - rhs.nanoticks, 1)
+        };
+        Duration {
+            ticks: self.ticks.wrapping_sub(rhs.ticks).wrapping_sub(borrow),
+            nanoticks,
+        }
+    }
+
+    /// Wrapping multiplication by a scalar, modulo [`Duration::MAX`] + 1 nanotick.
+    pub const fn wrapping_mul(self, rhs: u64) -> Duration {
+        // the carry is below `rhs` since nanoticks are below one tick, so it fits in a u64
+        let nanoticks = self.nanoticks as u128 * rhs as u128;
+        let carry = (nanoticks / NANOTICKS_PER_TICK as u128) as u64;
+        Duration {
+            ticks: self.ticks.wrapping_mul(rhs).wrapping_add(carry),
+            nanoticks: (nanoticks % NANOTICKS_PER_TICK as u128) as u32,
+        }
+    }
+}
+
+// Operator impls panic on overflow, in keeping with Duration::new (and std::time::Duration).
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
 }
 
+impl AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, rhs: Duration) -> Duration {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<u64> for Duration {
+    type Output = Duration;
+
+    fn mul(self, rhs: u64) -> Duration {
+        self.checked_mul(rhs)
+            .expect("overflow when multiplying duration by scalar")
+    }
+}
+
+impl Mul<Duration> for u64 {
+    type Output = Duration;
+
+    fn mul(self, rhs: Duration) -> Duration {
+        rhs * self
+    }
+}
+
+impl MulAssign<u64> for Duration {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div<u64> for Duration {
+    type Output = Duration;
+
+    fn div(self, rhs: u64) -> Duration {
+        self.checked_div(rhs)
+            .expect("divide by zero error when dividing duration by scalar")
+    }
+}
+
+impl DivAssign<u64> for Duration {
+    fn div_assign(&mut self, rhs: u64) {
+        *self = *self / rhs;
+    }
+}
+
+impl Rem for Duration {
+    type Output = Duration;
+
+    fn rem(self, rhs: Duration) -> Duration {
+        self.checked_rem(rhs)
+            .expect("divide by zero error when taking remainder of durations")
+    }
+}
+
+impl RemAssign for Duration {
+    fn rem_assign(&mut self, rhs: Duration) {
+        *self = *self % rhs;
+    }
+}
+
+impl Sum for Duration {
+    fn sum<I: Iterator<Item = Duration>>(iter: I) -> Duration {
+        iter.fold(Duration::ZERO, |acc, d| acc + d)
+    }
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+    fn sum<I: Iterator<Item = &'a Duration>>(iter: I) -> Duration {
+        iter.copied().sum()
+    }
+}
+
+/// Writes the sub-tick digits requested by the formatter's precision (e.g. `{:.3}`), if any.
+///
+/// Digits are truncated rather than rounded so that a time never displays as a tick it has not
+/// yet reached. Precision beyond nine digits (single nanoticks) is capped.
+fn fmt_subtick(fmter: &mut fmt::Formatter, nanoticks: u32) -> fmt::Result {
+    match fmter.precision() {
+        None | Some(0) => Ok(()),
+        Some(precision) => {
+            let digits = precision.min(9);
+            let fraction = nanoticks / 10_u32.pow(9 - digits as u32);
+            write!(fmter, ".{fraction:0digits$}")
+        }
+    }
+}
+
+/// Formats as `II:CC:TT`, with sub-tick digits following when a precision is given.
 impl fmt::Display for Duration {
     fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
         let (ints, cents, ticks) = self.time_components();
-        write!(fmter, "{ints:02}:{cents:02}:{ticks:02}")
+        write!(fmter, "{ints:02}:{cents:02}:{ticks:02}")?;
+        fmt_subtick(fmter, self.nanoticks)
     }
 }
 
+/// How to round a conversion that lands between two whole ticks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round down to the previous whole tick.
+    Floor,
+    /// Round to the closest tick, with exact halves rounding up.
+    Nearest,
+    /// Round up to the next whole tick.
+    Ceil,
+}
+
+impl Duration {
+    /// Converts from a standard library duration, rounding to a whole tick as requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rounded number of ticks does not fit in a [`Duration`].
+    pub fn from_std(
+        duration: std::time::Duration,
+        rounding: Rounding,
+    ) -> Result<Duration, std::num::TryFromIntError> {
+        let nanos = duration.as_nanos();
+        let per_tick = epochs::NANOS_PER_TICK as u128;
+        let ticks = match rounding {
+            Rounding::Floor => nanos / per_tick,
+            Rounding::Nearest => (nanos + per_tick / 2) / per_tick,
+            Rounding::Ceil => nanos.div_ceil(per_tick),
+        };
+        Ok(Duration::from_ticks(ticks.try_into()?))
+    }
+
+    /// Converts from a standard library duration, rounding down to a whole tick and also
+    /// returning the sub-tick remainder that was dropped.
+    ///
+    /// Carrying the remainder into the next conversion avoids drift when converting many
+    /// durations in sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of whole ticks does not fit in a [`Duration`].
+    pub fn from_std_with_remainder(
+        duration: std::time::Duration,
+    ) -> Result<(Duration, std::time::Duration), std::num::TryFromIntError> {
+        let whole = Duration::from_std(duration, Rounding::Floor)?;
+        Ok((whole, duration - std::time::Duration::from(whole)))
+    }
+}
+
+/// Converts from a standard library duration, rounding down to a whole nanotick.
+///
+/// Nanoticks are finer than nanoseconds, so converting back with [`std::time::Duration::from`]
+/// recovers the original duration. See [`Duration::from_std`] to round to whole ticks instead.
 impl TryFrom<std::time::Duration> for Duration {
     type Error = std::num::TryFromIntError;
 
     fn try_from(duration: std::time::Duration) -> Result<Self, Self::Error> {
-        let ticks: u128 = duration.as_micros() / 86_400;
-        match ticks.try_into() {
-            Ok(ticks) => Ok(Duration::new(0, 0, ticks)),
-            Err(err) => Err(err),
-        }
+        let nanos = duration.as_nanos();
+        let ticks = (nanos / epochs::NANOS_PER_TICK as u128).try_into()?;
+        let subtick_nanos = (nanos % epochs::NANOS_PER_TICK as u128) as u32;
+        Ok(Duration {
+            ticks,
+            nanoticks: epochs::nanos_to_nanoticks(subtick_nanos),
+        })
+    }
+}
+
+/// Converts to a standard library duration, rounding sub-tick precision to the nearest
+/// nanosecond. Whole ticks are a whole number of nanoseconds, so they convert exactly.
+impl From<Duration> for std::time::Duration {
+    fn from(duration: Duration) -> Self {
+        let nanos = duration.ticks as u128 * epochs::NANOS_PER_TICK as u128
+            + epochs::nanoticks_to_nanos(duration.nanoticks) as u128;
+        std::time::Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
     }
 }
 
 /// A date and time of a local system in the decimalized C10 calendar and clock.
+///
+/// Times are stored as signed ticks relative to the Unix epoch, so instants before 1970 are
+/// supported back to [`SystemTime::MIN`]. Like [`Duration`], times keep a sub-tick fraction
+/// to the nanotick.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SystemTime {
-    ticks: u64,
+    ticks: i64,
+    // always counts forward from `ticks`, even before the epoch
+    nanoticks: u32,
 }
 
 impl SystemTime {
+    /// The Unix epoch, New Years 1970 (UTC), from which all [`SystemTime`]s are measured.
+    pub const UNIX_EPOCH: SystemTime = SystemTime {
+        ticks: 0,
+        nanoticks: 0,
+    };
+
+    /// The earliest representable time: the start of the first year which begins within range.
+    pub const MIN: SystemTime = SystemTime {
+        ticks: epochs::year_to_ticks(epochs::year_from_ticks(i64::MIN) + 1),
+        nanoticks: 0,
+    };
+
+    /// The latest representable time.
+    pub const MAX: SystemTime = SystemTime {
+        ticks: i64::MAX,
+        nanoticks: NANOTICKS_PER_TICK - 1,
+    };
+
     /// Gets the current system time as a SystemTime.
     ///
     /// # Panics
@@ -104,50 +501,452 @@ impl SystemTime {
     pub fn now() -> SystemTime {
         // get system time
         let (secs, nsecs) = {
-            let ts: *mut timespec = std::mem::MaybeUninit::uninit().as_mut_ptr();
+            let mut ts = std::mem::MaybeUninit::<timespec>::uninit();
 
             // SAFETY: we verify the return value of the external function call was successful
             unsafe {
-                match clock_gettime(CLOCK_REALTIME, ts) {
-                    0 => ((*ts).tv_sec, (*ts).tv_nsec),
+                match clock_gettime(CLOCK_REALTIME, ts.as_mut_ptr()) {
+                    0 => {
+                        let ts = ts.assume_init();
+                        (ts.tv_sec, ts.tv_nsec)
+                    }
                     errno => panic!("clock_gettime failed with {errno}"),
                 }
             }
         };
 
-        // compute the number of ticks this way
-        // 1 tick = 0.0864 seconds ==> 625 ticks = 54 seconds
-        let sec_ticks = secs * 625 / 54;
-        let nsec_ticks = nsecs * 625 / 54_000_000_000;
+        let nanos = secs as i128 * 1_000_000_000 + nsecs as i128;
+        match SystemTime::from_unix_nanos(nanos) {
+            Ok(now) => now,
+            Err(err) => panic!("clock_gettime result unrepresentable: {err}"),
+        }
+    }
+
+    /// Creates a [`SystemTime`] from a number of ticks since the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Timestamp`] if `ticks` lies before [`SystemTime::MIN`].
+    pub const fn from_ticks(ticks: i64) -> Result<SystemTime, RangeError> {
+        if ticks < SystemTime::MIN.ticks {
+            return Err(RangeError::Timestamp);
+        }
+        Ok(SystemTime {
+            ticks,
+            nanoticks: 0,
+        })
+    }
+
+    /// Creates a [`SystemTime`] from a number of nanoticks (billionths of a tick) since the
+    /// Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Timestamp`] if the time cannot be represented.
+    pub const fn from_nanoticks(nanoticks: i128) -> Result<SystemTime, RangeError> {
+        let ticks = nanoticks.div_euclid(NANOTICKS_PER_TICK as i128);
+        if ticks > i64::MAX as i128 || ticks < SystemTime::MIN.ticks as i128 {
+            return Err(RangeError::Timestamp);
+        }
+        Ok(SystemTime {
+            ticks: ticks as i64,
+            nanoticks: nanoticks.rem_euclid(NANOTICKS_PER_TICK as i128) as u32,
+        })
+    }
+
+    /// Creates a [`SystemTime`] from Unix time in seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Timestamp`] if the time cannot be represented.
+    pub const fn from_unix_seconds(secs: i64) -> Result<SystemTime, RangeError> {
+        SystemTime::from_unix_nanos(secs as i128 * 1_000_000_000)
+    }
 
-        SystemTime {
-            ticks: (sec_ticks + nsec_ticks) as u64,
+    /// Creates a [`SystemTime`] from Unix time in nanoseconds, rounding down to the nanotick.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Timestamp`] if the time cannot be represented.
+    pub const fn from_unix_nanos(nanos: i128) -> Result<SystemTime, RangeError> {
+        let ticks = epochs::nanos_to_ticks(nanos);
+        if ticks > i64::MAX as i128 || ticks < SystemTime::MIN.ticks as i128 {
+            return Err(RangeError::Timestamp);
+        }
+        let subtick_nanos = (nanos - epochs::ticks_to_nanos(ticks as i64)) as u32;
+        Ok(SystemTime {
+            ticks: ticks as i64,
+            nanoticks: epochs::nanos_to_nanoticks(subtick_nanos),
+        })
+    }
+
+    /// Returns an error unless `year` lies within the years of representable times.
+    pub(crate) fn check_year(year: i64) -> Result<(), RangeError> {
+        let (min_year, _, _) = SystemTime::MIN.date_components();
+        let (max_year, _, _) = SystemTime::MAX.date_components();
+        if year < min_year || year > max_year {
+            return Err(RangeError::Component {
+                component: Component::Year,
+                value: year,
+                min: min_year,
+                max: max_year,
+            });
+        }
+        Ok(())
+    }
+
+    /// Creates a [`SystemTime`] from C10 date and time components, as returned by
+    /// [`SystemTime::date_components`] and [`SystemTime::time_components`].
+    ///
+    /// Decadays and days count from 1, while intervals, centivals, and ticks count from 0. The
+    /// 37th decaday holds only the 5 (or 6, in leap years) days remaining in the year.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] naming the first component out of its valid range, or
+    /// [`RangeError::Timestamp`] if the resulting time cannot be represented.
+    pub fn from_c10_components(
+        year: i64,
+        decaday: u64,
+        day: u64,
+        interval: u64,
+        centival: u64,
+        tick: u64,
+    ) -> Result<SystemTime, RangeError> {
+        SystemTime::check_year(year)?;
+
+        let max_day = match decaday {
+            1..=36 => 10,
+            37 if epochs::is_leap_year(year) => 6,
+            37 => 5,
+            _ => return Err(Component::Decaday.out_of_range(decaday, 1, 37)),
+        };
+        if !(1..=max_day).contains(&day) {
+            return Err(Component::Day.out_of_range(day, 1, max_day));
+        }
+        if interval > 99 {
+            return Err(Component::Interval.out_of_range(interval, 0, 99));
+        }
+        if centival > 99 {
+            return Err(Component::Centival.out_of_range(centival, 0, 99));
+        }
+        if tick > 99 {
+            return Err(Component::Tick.out_of_range(tick, 0, 99));
         }
+
+        let dayinyear = (decaday - 1) * 10 + (day - 1);
+        let since_new_year = dayinyear * DAY.ticks + Duration::new(interval, centival, tick).ticks;
+        SystemTime::from_ticks(epochs::year_to_ticks(year))?
+            .checked_add(Duration::from_ticks(since_new_year))
+            .ok_or(RangeError::Timestamp)
+    }
+
+    /// Returns the number of whole ticks since the Unix epoch, rounded toward the past.
+    pub const fn as_ticks(&self) -> i64 {
+        self.ticks
+    }
+
+    /// Returns the number of nanoticks since the Unix epoch.
+    pub const fn as_nanoticks(&self) -> i128 {
+        self.ticks as i128 * NANOTICKS_PER_TICK as i128 + self.nanoticks as i128
+    }
+
+    /// Returns the Unix time in nanoseconds, rounding sub-tick precision to the nearest
+    /// nanosecond.
+    pub(crate) const fn unix_nanos(&self) -> i128 {
+        epochs::ticks_to_nanos(self.ticks) + epochs::nanoticks_to_nanos(self.nanoticks) as i128
+    }
+
+    /// Returns this time moved by `nanoticks`, or unchanged if that cannot be represented.
+    pub(crate) fn shifted_nanoticks(&self, nanoticks: i128) -> SystemTime {
+        self.as_nanoticks()
+            .checked_add(nanoticks)
+            .and_then(|nanoticks| SystemTime::from_nanoticks(nanoticks).ok())
+            .unwrap_or(*self)
+    }
+
+    /// Returns how far this time lies into its current tick, in nanoticks.
+    pub const fn subtick_nanoticks(&self) -> u32 {
+        self.nanoticks
+    }
+
+    /// Returns how far this time lies into its current tick, in whole microticks.
+    pub const fn subtick_microticks(&self) -> u32 {
+        self.nanoticks / 1000
     }
 
     /// Returns the interval, centival, and tick components of the timestamp's day.
     pub fn time_components(&self) -> (u64, u64, u64) {
-        let ticks = self.ticks % 100;
-        let cents = (self.ticks / 100) % 100;
-        let ints = (self.ticks / (100 * 100)) % 100;
+        let dayticks = self.ticks.rem_euclid(100 * 100 * 100) as u64;
+        let ticks = dayticks % 100;
+        let cents = (dayticks / 100) % 100;
+        let ints = dayticks / (100 * 100);
         (ints, cents, ticks)
     }
 
     /// Returns the year, decaday, and day components of the timestamp's date.
-    pub fn date_components(&self) -> (u64, u64, u64) {
-        let year: u64 = epochs::year_from_ticks(self.ticks).try_into().unwrap();
-        let dayinyear = (self.ticks - epochs::year_to_ticks(year as usize)) / 1_000_000;
+    ///
+    /// The days after the 36th decaday are counted as a short 37th; [`SystemTime::date`] names
+    /// them instead.
+    pub fn date_components(&self) -> (i64, u64, u64) {
+        let year = epochs::year_from_ticks(self.ticks);
+        let dayinyear = ((self.ticks - epochs::year_to_ticks(year)) / 1_000_000) as u64;
         let decaday = (dayinyear / 10) + 1;
         let day = (dayinyear % 10) + 1;
         (year, decaday, day)
     }
+
+    /// Returns the amount of time elapsed from an earlier point in time.
+    ///
+    /// # Errors
+    ///
+    /// If `earlier` is later than `self`, returns a [`SystemTimeError`] holding how far
+    /// in the future `earlier` lies.
+    pub fn duration_since(&self, earlier: SystemTime) -> Result<Duration, SystemTimeError> {
+        // the span between any two representable times fits within a Duration
+        let nanoticks = self.as_nanoticks() - earlier.as_nanoticks();
+        let duration = Duration::from_nanoticks(nanoticks.unsigned_abs());
+        if nanoticks >= 0 {
+            Ok(duration)
+        } else {
+            Err(SystemTimeError(duration))
+        }
+    }
+
+    /// Returns the amount of time elapsed since this time was created.
+    ///
+    /// # Errors
+    ///
+    /// If the system clock has been adjusted to before this time, returns a
+    /// [`SystemTimeError`] holding how far in the future this time lies.
+    pub fn elapsed(&self) -> Result<Duration, SystemTimeError> {
+        SystemTime::now().duration_since(*self)
+    }
+
+    /// Returns the offset of local time from UTC at this instant in seconds, in the zone
+    /// [`TimeZone::local`](zone::TimeZone::local) finds the first time one is needed.
+    ///
+    /// Returns 0 if the local zone cannot be read.
+    pub fn local_offset(&self) -> i64 {
+        zone::TimeZone::local_cached()
+            .local_type(*self)
+            .offset
+            .into()
+    }
+
+    /// Returns this time as read on the local wall clock, that is, shifted by
+    /// [`SystemTime::local_offset`].
+    ///
+    /// The result is only meaningful for display; it no longer denotes the same instant. The same
+    /// goes for the other wall clocks this crate reads times on, such as
+    /// [`SystemTime::to_offset`] and [`SystemTime::to_mean_solar`]. Each returns the time
+    /// unchanged if the shifted time cannot be represented.
+    pub fn to_local(&self) -> SystemTime {
+        self.shifted_nanoticks(seconds_to_nanoticks(self.local_offset()))
+    }
+
+    /// Returns `Some(t)` where `t` is the time `self + duration`, or `None` if `t` cannot be
+    /// represented.
+    pub const fn checked_add(&self, duration: Duration) -> Option<SystemTime> {
+        match SystemTime::from_nanoticks(self.as_nanoticks() + duration.as_nanoticks() as i128) {
+            Ok(time) => Some(time),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns `Some(t)` where `t` is the time `self - duration`, or `None` if `t` cannot be
+    /// represented.
+    pub const fn checked_sub(&self, duration: Duration) -> Option<SystemTime> {
+        match SystemTime::from_nanoticks(self.as_nanoticks() - duration.as_nanoticks() as i128) {
+            Ok(time) => Some(time),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Converts from the standard library's system time, rounding toward the past to a whole
+/// nanotick.
+impl TryFrom<std::time::SystemTime> for SystemTime {
+    type Error = RangeError;
+
+    fn try_from(time: std::time::SystemTime) -> Result<Self, Self::Error> {
+        let nanos = match time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => after.as_nanos() as i128,
+            Err(before) => -(before.duration().as_nanos() as i128),
+        };
+        SystemTime::from_unix_nanos(nanos)
+    }
+}
+
+/// Converts to the standard library's system time, rounding sub-tick precision to the nearest
+/// nanosecond. Times converted from the standard library convert back exactly.
+impl TryFrom<SystemTime> for std::time::SystemTime {
+    type Error = RangeError;
+
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        let nanos = time.unix_nanos();
+        let offset = std::time::Duration::new(
+            (nanos.unsigned_abs() / 1_000_000_000) as u64,
+            (nanos.unsigned_abs() % 1_000_000_000) as u32,
+        );
+        let converted = if nanos >= 0 {
+            std::time::UNIX_EPOCH.checked_add(offset)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(offset)
+        };
+        converted.ok_or(RangeError::Timestamp)
+    }
+}
+
+impl Add<Duration> for SystemTime {
+    type Output = SystemTime;
+
+    fn add(self, rhs: Duration) -> SystemTime {
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to time")
+    }
+}
+
+impl AddAssign<Duration> for SystemTime {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for SystemTime {
+    type Output = SystemTime;
+
+    fn sub(self, rhs: Duration) -> SystemTime {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from time")
+    }
+}
+
+impl SubAssign<Duration> for SystemTime {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for SystemTime {
+    type Output = Duration;
+
+    /// Returns the duration between two times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is later than `self`; see [`SystemTime::duration_since`] for a
+    /// non-panicking alternative.
+    fn sub(self, rhs: SystemTime) -> Duration {
+        match self.duration_since(rhs) {
+            Ok(duration) => duration,
+            Err(err) => panic!("{err}"),
+        }
+    }
 }
 
+/// An error returned from [`SystemTime::duration_since`] and [`SystemTime::elapsed`] when the
+/// "earlier" time is actually later.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SystemTimeError(Duration);
+
+impl SystemTimeError {
+    /// Returns how far the second time lies after the first.
+    pub fn duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl fmt::Display for SystemTimeError {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmter,
+            "second time provided was later than self by {}",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for SystemTimeError {}
+
+/// A component of a C10 date or time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Component {
+    Year,
+    Decaday,
+    Day,
+    Interval,
+    Centival,
+    Tick,
+}
+
+impl Component {
+    fn out_of_range(self, value: u64, min: u64, max: u64) -> RangeError {
+        RangeError::Component {
+            component: self,
+            value: value.try_into().unwrap_or(i64::MAX),
+            min: min as i64,
+            max: max as i64,
+        }
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Component::Year => "year",
+            Component::Decaday => "decaday",
+            Component::Day => "day",
+            Component::Interval => "interval",
+            Component::Centival => "centival",
+            Component::Tick => "tick",
+        };
+        fmter.write_str(name)
+    }
+}
+
+/// An error returned when constructing a [`SystemTime`] from values it cannot represent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RangeError {
+    /// A date or time component was outside of its valid range `min..=max`.
+    Component {
+        component: Component,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+    /// The instant lies outside of [`SystemTime::MIN`]..=[`SystemTime::MAX`].
+    Timestamp,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RangeError::Component {
+                component,
+                value,
+                min,
+                max,
+            } => write!(
+                fmter,
+                "{component} {value} is outside of the range {min}..={max}"
+            ),
+            RangeError::Timestamp => {
+                write!(fmter, "timestamp is outside of the representable range")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Formats as `YYYY DD.dd II:CC:TT`, with sub-tick digits following when a precision is given.
 impl fmt::Display for SystemTime {
     fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
         let (ints, cents, ticks) = self.time_components();
         let (year, decaday, day) = self.date_components();
         write!(fmter, "{year:4} {decaday:2}.{day:02} ")?;
-        write!(fmter, "{ints:02}:{cents:02}:{ticks:02}")
+        write!(fmter, "{ints:02}:{cents:02}:{ticks:02}")?;
+        fmt_subtick(fmter, self.nanoticks)
     }
 }
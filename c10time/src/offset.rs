@@ -0,0 +1,377 @@
+//! Offsets from UTC in C10 units, and decimal time zones built from them.
+//!
+//! An [`Offset`] is kept to the centival, so `+2.50` is two and a half intervals (36 minutes)
+//! ahead of UTC. Conventional offsets rarely fall on a whole centival (`+05:30` is 22.9166…
+//! intervals), so converting to and from them takes a [`Rounding`].
+//!
+//! [`DecimalZone`]s divide the world into zones five intervals wide, centered on whole multiples
+//! of five intervals, so that teams can agree on a shared decimal offset:
+//!
+//! ```
+//! use c10::offset::{DecimalZone, Offset};
+//! use c10::Rounding;
+//!
+//! // India's +05:30, to the nearest centival
+//! let india = Offset::from_seconds(5 * 3_600 + 30 * 60, Rounding::Nearest).unwrap();
+//! assert_eq!(india.to_string(), "+22.92");
+//! assert_eq!(DecimalZone::containing(india).unwrap().to_string(), "C10+25");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parse::{Cursor, ParseError, ParseErrorKind};
+use crate::{Component, RangeError, Rounding, SystemTime, CENTIVAL};
+
+/// Centivals in an interval.
+const CENTIVALS_PER_INTERVAL: i32 = 100;
+
+/// Offsets must lie within a day of UTC.
+const MAX_CENTIVALS: i32 = 100 * CENTIVALS_PER_INTERVAL - 1;
+
+/// An offset of local time from UTC in whole centivals, positive east of Greenwich.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset {
+    centivals: i32,
+}
+
+impl Offset {
+    /// No offset: UTC itself.
+    pub const UTC: Offset = Offset { centivals: 0 };
+
+    /// Creates an offset of `centivals` ahead of UTC (behind it when negative).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] for the interval if the offset is a day or more.
+    pub const fn from_centivals(centivals: i32) -> Result<Offset, RangeError> {
+        if centivals.unsigned_abs() > MAX_CENTIVALS as u32 {
+            return Err(RangeError::Component {
+                component: Component::Interval,
+                value: (centivals / CENTIVALS_PER_INTERVAL) as i64,
+                min: -99,
+                max: 99,
+            });
+        }
+        Ok(Offset { centivals })
+    }
+
+    /// Converts a conventional offset in seconds, such as `+05:30` as 19,800, rounding to a
+    /// whole centival (8.64 seconds) as requested. Halves round toward the east.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Component`] for the interval if the rounded offset is a day or
+    /// more.
+    pub fn from_seconds(secs: i32, rounding: Rounding) -> Result<Offset, RangeError> {
+        // 1 centival = 216/25 seconds
+        let centivals = divide(i64::from(secs) * 25, 216, rounding);
+        let centivals = centivals.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+        Offset::from_centivals(centivals)
+    }
+
+    /// Returns the number of centivals ahead of UTC (negative when behind it).
+    pub const fn as_centivals(&self) -> i32 {
+        self.centivals
+    }
+
+    /// Converts to a conventional offset in seconds, rounding to a whole second as requested.
+    /// Halves round toward the east.
+    pub fn to_seconds(&self, rounding: Rounding) -> i32 {
+        // within a day of UTC, so the result fits
+        divide(i64::from(self.centivals) * 216, 25, rounding) as i32
+    }
+
+    /// Returns the decimal zone this offset falls in, if any.
+    pub fn zone(&self) -> Option<DecimalZone> {
+        DecimalZone::containing(*self)
+    }
+}
+
+/// Divides `numerator` by a positive `denominator`, rounding as requested.
+fn divide(numerator: i64, denominator: i64, rounding: Rounding) -> i64 {
+    match rounding {
+        Rounding::Floor => numerator.div_euclid(denominator),
+        Rounding::Nearest => (2 * numerator + denominator).div_euclid(2 * denominator),
+        Rounding::Ceil => -(-numerator).div_euclid(denominator),
+    }
+}
+
+impl SystemTime {
+    /// Returns this time as read on a wall clock `offset` from UTC.
+    pub fn to_offset(&self, offset: Offset) -> SystemTime {
+        self.shifted_nanoticks(offset.centivals as i128 * CENTIVAL.as_nanoticks() as i128)
+    }
+}
+
+/// Formats as a signed number of intervals with centivals as hundredths, such as `+2.50` or
+/// `-14.58`.
+impl fmt::Display for Offset {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.centivals < 0 { '-' } else { '+' };
+        let centivals = self.centivals.unsigned_abs();
+        let (intervals, centivals) = (centivals / 100, centivals % 100);
+        write!(fmter, "{sign}{intervals}.{centivals:02}")
+    }
+}
+
+/// Parses a number of intervals with an optional sign and up to two decimal places, as written
+/// by `Display`. `+2.5` and `+2.50` are both two intervals and fifty centivals.
+impl FromStr for Offset {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(text);
+        let negative = cursor.eat('-');
+        if !negative {
+            cursor.eat('+');
+        }
+        let intervals = cursor.number(Component::Interval, 2)? as i32;
+        let mut centivals = 0;
+        if cursor.eat('.') {
+            let start = cursor.pos();
+            let digits = cursor.digits(2);
+            centivals = match digits.len() {
+                1 => digits.parse::<i32>().unwrap_or(0) * 10,
+                2 => digits.parse().unwrap_or(0),
+                _ => {
+                    let invalid = ParseErrorKind::Invalid(Component::Centival);
+                    return Err(ParseError::new(invalid, start));
+                }
+            };
+        }
+        cursor.finish()?;
+
+        let centivals = intervals * CENTIVALS_PER_INTERVAL + centivals;
+        Ok(Offset {
+            centivals: if negative { -centivals } else { centivals },
+        })
+    }
+}
+
+/// A decimal time zone: a band of offsets five intervals (1.2 hours) wide, named after the
+/// offset at its center, from `C10-50` to `C10+60`.
+///
+/// Like conventional zones, which run from UTC−12 to UTC+14, decimal zones extend past half a
+/// day east of UTC so that no country need be split by the date line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DecimalZone {
+    // the offset at the center, in intervals
+    intervals: i8,
+}
+
+impl DecimalZone {
+    /// The width of each zone.
+    pub const WIDTH: Offset = Offset {
+        centivals: 5 * CENTIVALS_PER_INTERVAL,
+    };
+
+    /// The westernmost zone.
+    pub const WEST: DecimalZone = DecimalZone { intervals: -50 };
+
+    /// The easternmost zone.
+    pub const EAST: DecimalZone = DecimalZone { intervals: 60 };
+
+    /// Returns every zone, from west to east.
+    pub fn all() -> impl Iterator<Item = DecimalZone> {
+        (DecimalZone::WEST.intervals..=DecimalZone::EAST.intervals)
+            .step_by(5)
+            .map(|intervals| DecimalZone { intervals })
+    }
+
+    /// Returns the zone whose band holds `offset`. Each zone holds offsets from half a width
+    /// west of its center up to, but not including, half a width east of it.
+    ///
+    /// Returns `None` for offsets beyond the outermost zones.
+    pub fn containing(offset: Offset) -> Option<DecimalZone> {
+        let width = DecimalZone::WIDTH.centivals;
+        let index = (offset.centivals + width / 2).div_euclid(width);
+        let intervals = i8::try_from(index * 5).ok()?;
+        let zone = DecimalZone { intervals };
+        (DecimalZone::WEST..=DecimalZone::EAST)
+            .contains(&zone)
+            .then_some(zone)
+    }
+
+    /// Returns the offset every clock in the zone keeps.
+    pub fn offset(&self) -> Offset {
+        Offset {
+            centivals: i32::from(self.intervals) * CENTIVALS_PER_INTERVAL,
+        }
+    }
+}
+
+/// Formats as the zone's name, such as `C10+25` or `C10-5`.
+impl fmt::Display for DecimalZone {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmter, "C10{:+}", self.intervals)
+    }
+}
+
+/// Parses a zone's name, as written by `Display`.
+impl FromStr for DecimalZone {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseError::new(ParseErrorKind::Invalid(Component::Interval), 0);
+        let offset = text.strip_prefix("C10").ok_or_else(invalid)?;
+        if !offset.starts_with(['+', '-']) {
+            return Err(invalid());
+        }
+        let intervals: i8 = offset.parse().map_err(|_| invalid())?;
+        DecimalZone::all()
+            .find(|zone| zone.intervals == intervals)
+            .ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i32 = 3_600;
+
+    fn offset(text: &str) -> Offset {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn from_conventional() {
+        let india = 5 * HOUR + 30 * 60;
+        assert_eq!(
+            Offset::from_seconds(india, Rounding::Floor).unwrap(),
+            offset("+22.91")
+        );
+        assert_eq!(
+            Offset::from_seconds(india, Rounding::Nearest).unwrap(),
+            offset("+22.92")
+        );
+        assert_eq!(
+            Offset::from_seconds(india, Rounding::Ceil).unwrap(),
+            offset("+22.92")
+        );
+
+        // rounding is toward the past or future along the line, not toward zero
+        let newfoundland = -(3 * HOUR + 30 * 60);
+        let floor = Offset::from_seconds(newfoundland, Rounding::Floor).unwrap();
+        assert_eq!(floor, offset("-14.59"));
+        let nearest = Offset::from_seconds(newfoundland, Rounding::Nearest).unwrap();
+        assert_eq!(nearest, offset("-14.58"));
+        let ceil = Offset::from_seconds(newfoundland, Rounding::Ceil).unwrap();
+        assert_eq!(ceil, offset("-14.58"));
+
+        // offsets in whole multiples of 3.6 minutes are exact
+        for rounding in [Rounding::Floor, Rounding::Nearest, Rounding::Ceil] {
+            let exact = Offset::from_seconds(-12 * HOUR, rounding).unwrap();
+            assert_eq!(exact, offset("-50"));
+            assert_eq!(exact.to_seconds(rounding), -12 * HOUR);
+        }
+
+        // halves round east: 108 seconds is 12.5 centivals
+        assert_eq!(
+            Offset::from_seconds(108, Rounding::Nearest).unwrap(),
+            offset("+0.13")
+        );
+        assert_eq!(
+            Offset::from_seconds(-108, Rounding::Nearest).unwrap(),
+            offset("-0.12")
+        );
+
+        assert!(Offset::from_seconds(24 * HOUR, Rounding::Floor).is_err());
+        assert!(Offset::from_seconds(24 * HOUR - 9, Rounding::Floor).is_ok());
+        assert!(Offset::from_seconds(i32::MIN, Rounding::Floor).is_err());
+    }
+
+    #[test]
+    fn to_conventional() {
+        // 1 centival is 8.64 seconds
+        let india = offset("+22.92");
+        assert_eq!(india.to_seconds(Rounding::Floor), 19_802);
+        assert_eq!(india.to_seconds(Rounding::Nearest), 19_803);
+        assert_eq!(india.to_seconds(Rounding::Ceil), 19_803);
+        assert_eq!(offset("-0.01").to_seconds(Rounding::Floor), -9);
+        assert_eq!(offset("-0.01").to_seconds(Rounding::Ceil), -8);
+
+        // every whole-minute offset survives the round trip to the nearest centival and back
+        for minutes in -12 * 60..=14 * 60 {
+            let offset = Offset::from_seconds(minutes * 60, Rounding::Nearest).unwrap();
+            let back = offset.to_seconds(Rounding::Nearest);
+            assert!((back - minutes * 60).abs() <= 5, "{minutes}");
+        }
+    }
+
+    #[test]
+    fn display_and_parse() {
+        assert_eq!(Offset::UTC.to_string(), "+0.00");
+        assert_eq!(offset("2.5").to_string(), "+2.50");
+        assert_eq!(offset("-4.05").to_string(), "-4.05");
+        assert_eq!(offset("-0.5").as_centivals(), -50);
+        assert_eq!(offset("+99.99").as_centivals(), 9_999);
+        for invalid in ["", "+", "2.", "2.500", "100", "+-1", "1 interval", "2.5i"] {
+            assert!(invalid.parse::<Offset>().is_err(), "{invalid:?}");
+        }
+        assert!(Offset::from_centivals(-10_000).is_err());
+    }
+
+    #[test]
+    fn decimal_zones() {
+        let zones: Vec<String> = DecimalZone::all().map(|zone| zone.to_string()).collect();
+        assert_eq!(zones.len(), 23);
+        assert_eq!(zones[0], "C10-50");
+        assert_eq!(zones[10], "C10+0");
+        assert_eq!(zones[22], "C10+60");
+
+        let zone = |hours: i32| {
+            let offset = Offset::from_seconds(hours * HOUR, Rounding::Nearest).unwrap();
+            offset.zone().map(|zone| zone.to_string())
+        };
+        assert_eq!(zone(0).as_deref(), Some("C10+0"));
+        assert_eq!(zone(1).as_deref(), Some("C10+5"));
+        assert_eq!(zone(-5).as_deref(), Some("C10-20"));
+        assert_eq!(zone(-12).as_deref(), Some("C10-50"));
+        assert_eq!(zone(14).as_deref(), Some("C10+60"));
+        assert_eq!(zone(-13), None);
+        assert_eq!(zone(15), None);
+
+        // each zone holds its western boundary but not its eastern one
+        assert_eq!(
+            DecimalZone::containing(offset("-2.5")),
+            Some(DecimalZone { intervals: 0 })
+        );
+        assert_eq!(
+            DecimalZone::containing(offset("+2.49")),
+            Some(DecimalZone { intervals: 0 })
+        );
+        assert_eq!(
+            DecimalZone::containing(offset("+2.5")).map(|zone| zone.offset()),
+            Some(offset("+5"))
+        );
+
+        assert_eq!(
+            "C10+25".parse::<DecimalZone>().unwrap().offset(),
+            offset("+25")
+        );
+        for invalid in ["C10", "C1025", "C10+3", "C10+65", "UTC+5", "c10+5"] {
+            assert!(invalid.parse::<DecimalZone>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn shifting_times() {
+        let epoch = SystemTime::UNIX_EPOCH;
+        assert_eq!(
+            epoch.to_offset(offset("+2.50")).time_components(),
+            (2, 50, 0)
+        );
+        assert_eq!(
+            epoch.to_offset(offset("-2.50")).time_components(),
+            (97, 50, 0)
+        );
+        assert_eq!(
+            epoch.to_offset(offset("-2.50")).date_components(),
+            (1969, 37, 5)
+        );
+        assert_eq!(epoch.to_offset(Offset::UTC), epoch);
+        assert_eq!(SystemTime::MIN.to_offset(offset("-1")), SystemTime::MIN);
+    }
+}
@@ -0,0 +1,639 @@
+//! Parsing of C10 times and durations from text.
+//!
+//! [`Mode::Strict`] accepts exactly the layout written by the `Display` impls of [`SystemTime`]
+//! (`YYYY DD.dd II:CC:TT`) and [`Duration`] (`II:CC:TT`), optionally followed by sub-tick digits
+//! as written with a precision (e.g. `{:.3}`). [`Mode::Lenient`] additionally allows:
+//!
+//! - leading and trailing whitespace, and any run of whitespace between date and time
+//! - components written without padding, and years without a minimum width
+//! - a [`SystemTime`] without a time of day, meaning its first tick
+//! - a [`Duration`] of more than 99 intervals
+//! - sub-tick digits beyond the ninth (nanotick), which are truncated
+//!
+//! The `FromStr` impls parse leniently.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Component, Duration, RangeError, SystemTime, DAY};
+
+/// How closely input must follow the `Display` layout.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Accept only the exact layout produced by `Display`.
+    Strict,
+    /// Accept the `Display` layout along with unpadded and abbreviated variants.
+    Lenient,
+}
+
+/// The reason parsing failed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// A component was missing or not written as the expected number.
+    Invalid(Component),
+    /// The sub-tick digits following a `.` were missing or malformed.
+    InvalidSubtick,
+    /// An expected separator was missing.
+    Separator(char),
+    /// A component was well-formed but out of its valid range.
+    OutOfRange(RangeError),
+    /// Input continued after a complete time or duration.
+    Trailing,
+    /// A format string given to [`SystemTime::parse_format`] held an unrecognized specifier.
+    /// The error's position is within the format string rather than the input.
+    InvalidFormat,
+}
+
+/// An error returned when parsing a [`SystemTime`] or [`Duration`], pointing at the offending
+/// part of the input.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: ParseErrorKind, position: usize) -> ParseError {
+        ParseError { kind, position }
+    }
+
+    /// Returns the reason parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset into the input at which the offending part begins.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        let position = self.position;
+        match self.kind {
+            ParseErrorKind::Invalid(component) => {
+                write!(fmter, "invalid {component} at byte {position}")
+            }
+            ParseErrorKind::InvalidSubtick => {
+                write!(fmter, "invalid sub-tick digits at byte {position}")
+            }
+            ParseErrorKind::Separator(sep) => {
+                write!(fmter, "expected '{sep}' at byte {position}")
+            }
+            ParseErrorKind::OutOfRange(err) => write!(fmter, "{err} at byte {position}"),
+            ParseErrorKind::Trailing => write!(fmter, "unexpected input at byte {position}"),
+            ParseErrorKind::InvalidFormat => {
+                write!(fmter, "unrecognized format specifier at byte {position}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A position within input being parsed, shared by the fixed layouts here and by the
+/// specifier-driven parser.
+pub(crate) struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Cursor<'a> {
+        Cursor { input, pos: 0 }
+    }
+
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    /// Text consumed since `start`.
+    pub(crate) fn since(&self, start: usize) -> &'a str {
+        &self.input[start..self.pos]
+    }
+
+    /// Consumes `c` if it is next, returning whether it was.
+    pub(crate) fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(ParseError::new(ParseErrorKind::Separator(c), self.pos)),
+        }
+    }
+
+    /// Consumes any whitespace, returning how much was skipped.
+    pub(crate) fn skip_whitespace(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += self.peek().map_or(0, char::len_utf8);
+        }
+        self.pos - start
+    }
+
+    /// Consumes up to `max` ASCII digits, returning them.
+    pub(crate) fn digits(&mut self, max: usize) -> &'a str {
+        let start = self.pos;
+        let rest = &self.input.as_bytes()[start..];
+        let count = rest
+            .iter()
+            .take(max)
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        self.pos += count;
+        &self.input[start..self.pos]
+    }
+
+    /// Consumes an unsigned number of up to `max` digits for the given component.
+    pub(crate) fn number(&mut self, component: Component, max: usize) -> Result<u64, ParseError> {
+        let start = self.pos;
+        let invalid = ParseError::new(ParseErrorKind::Invalid(component), start);
+        let digits = self.digits(max);
+        if digits.is_empty() {
+            return Err(invalid);
+        }
+        digits.parse().map_err(|_| invalid)
+    }
+
+    /// Consumes a signed number of up to `max` digits for the given component.
+    pub(crate) fn signed(&mut self, component: Component, max: usize) -> Result<i64, ParseError> {
+        let start = self.pos;
+        let invalid = ParseError::new(ParseErrorKind::Invalid(component), start);
+        let negative = self.eat('-');
+        let digits = self.digits(max);
+        if digits.is_empty() {
+            return Err(invalid);
+        }
+        let magnitude: i128 = digits.parse().map_err(|_| invalid)?;
+        let value = if negative { -magnitude } else { magnitude };
+        value.try_into().map_err(|_| invalid)
+    }
+
+    /// Consumes sub-tick digits following a `.`, if present, returning them as nanoticks.
+    ///
+    /// At most nine digits are accepted, unless `truncate` allows any more to be dropped.
+    pub(crate) fn subtick(&mut self, truncate: bool) -> Result<u32, ParseError> {
+        if !self.eat('.') {
+            return Ok(0);
+        }
+        let start = self.pos;
+        let digits = self.digits(usize::MAX);
+        if digits.is_empty() || (digits.len() > 9 && !truncate) {
+            return Err(ParseError::new(ParseErrorKind::InvalidSubtick, start));
+        }
+        let kept = &digits[..digits.len().min(9)];
+        let scale = 10_u32.pow(9 - kept.len() as u32);
+        Ok(kept.parse::<u32>().unwrap_or(0) * scale)
+    }
+
+    /// Fails unless all input has been consumed.
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(ParseError::new(ParseErrorKind::Trailing, self.pos)),
+        }
+    }
+}
+
+/// In strict mode, checks that the text just parsed for a component is exactly its canonical
+/// rendering.
+fn canonical(
+    cursor: &Cursor,
+    mode: Mode,
+    start: usize,
+    component: Component,
+    expected: String,
+) -> Result<(), ParseError> {
+    if mode == Mode::Strict && cursor.since(start) != expected {
+        return Err(ParseError::new(ParseErrorKind::Invalid(component), start));
+    }
+    Ok(())
+}
+
+fn out_of_range(component: Component, value: u64, max: u64, position: usize) -> ParseError {
+    let err = RangeError::Component {
+        component,
+        value: value.try_into().unwrap_or(i64::MAX),
+        min: 0,
+        max: max as i64,
+    };
+    ParseError::new(ParseErrorKind::OutOfRange(err), position)
+}
+
+/// Parses `II:CC:TT[.fff]` at the cursor, returning the time of day (or duration) and where
+/// each of its components began.
+fn time_of_day(cursor: &mut Cursor, mode: Mode) -> Result<(Duration, [usize; 3]), ParseError> {
+    let wide = match mode {
+        Mode::Strict => 2,
+        Mode::Lenient => 20,
+    };
+
+    let ints_at = cursor.pos();
+    let ints = cursor.number(Component::Interval, wide)?;
+    canonical(
+        cursor,
+        mode,
+        ints_at,
+        Component::Interval,
+        format!("{ints:02}"),
+    )?;
+    cursor.expect(':')?;
+
+    let cents_at = cursor.pos();
+    let cents = cursor.number(Component::Centival, 3)?;
+    if cents > 99 {
+        return Err(out_of_range(Component::Centival, cents, 99, cents_at));
+    }
+    canonical(
+        cursor,
+        mode,
+        cents_at,
+        Component::Centival,
+        format!("{cents:02}"),
+    )?;
+    cursor.expect(':')?;
+
+    let ticks_at = cursor.pos();
+    let ticks = cursor.number(Component::Tick, 3)?;
+    if ticks > 99 {
+        return Err(out_of_range(Component::Tick, ticks, 99, ticks_at));
+    }
+    canonical(
+        cursor,
+        mode,
+        ticks_at,
+        Component::Tick,
+        format!("{ticks:02}"),
+    )?;
+
+    let nanoticks = cursor.subtick(mode == Mode::Lenient)?;
+
+    let whole = match ints.checked_mul(100 * 100) {
+        Some(iticks) => iticks.checked_add(cents * 100 + ticks),
+        None => None,
+    };
+    let max_ints = u64::MAX / (100 * 100);
+    let whole = whole.ok_or_else(|| out_of_range(Component::Interval, ints, max_ints, ints_at))?;
+    let duration = Duration::from_ticks(whole) + Duration::from_nanoticks(nanoticks as u128);
+
+    Ok((duration, [ints_at, cents_at, ticks_at]))
+}
+
+impl Duration {
+    /// Parses a duration written as `II:CC:TT`, optionally followed by sub-tick digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] locating the first part of `input` which does not conform to
+    /// `mode`.
+    pub fn parse(input: &str, mode: Mode) -> Result<Duration, ParseError> {
+        let mut cursor = Cursor::new(input);
+        if mode == Mode::Lenient {
+            cursor.skip_whitespace();
+        }
+        let (duration, _) = time_of_day(&mut cursor, mode)?;
+        if mode == Mode::Lenient {
+            cursor.skip_whitespace();
+        }
+        cursor.finish()?;
+        Ok(duration)
+    }
+}
+
+impl FromStr for Duration {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Duration::parse(s, Mode::Lenient)
+    }
+}
+
+impl SystemTime {
+    /// Parses a time written as `YYYY DD.dd II:CC:TT`, optionally followed by sub-tick digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] locating the first part of `input` which does not conform to
+    /// `mode` or holds an out-of-range value.
+    pub fn parse(input: &str, mode: Mode) -> Result<SystemTime, ParseError> {
+        let mut cursor = Cursor::new(input);
+
+        // the year is right-aligned to four columns, so strict mode only allows padding there
+        let year_at = cursor.pos();
+        cursor.skip_whitespace();
+        let year = cursor.signed(Component::Year, 20)?;
+        canonical(&cursor, mode, year_at, Component::Year, format!("{year:4}"))?;
+
+        // likewise the decaday is right-aligned to two columns
+        match mode {
+            Mode::Strict => cursor.expect(' ')?,
+            Mode::Lenient => {
+                if cursor.skip_whitespace() == 0 {
+                    return Err(ParseError::new(
+                        ParseErrorKind::Separator(' '),
+                        cursor.pos(),
+                    ));
+                }
+            }
+        }
+        let decaday_at = cursor.pos();
+        if mode == Mode::Strict {
+            cursor.eat(' ');
+        }
+        let decaday = cursor.number(Component::Decaday, 2)?;
+        canonical(
+            &cursor,
+            mode,
+            decaday_at,
+            Component::Decaday,
+            format!("{decaday:2}"),
+        )?;
+        cursor.expect('.')?;
+
+        let day_at = cursor.pos();
+        let day = cursor.number(Component::Day, 2)?;
+        canonical(&cursor, mode, day_at, Component::Day, format!("{day:02}"))?;
+
+        let (time, [ints_at, cents_at, ticks_at]) = match mode {
+            Mode::Strict => {
+                cursor.expect(' ')?;
+                time_of_day(&mut cursor, mode)?
+            }
+            Mode::Lenient => {
+                let gap = cursor.skip_whitespace();
+                if cursor.is_empty() {
+                    (Duration::ZERO, [cursor.pos(); 3])
+                } else if gap == 0 {
+                    return Err(ParseError::new(
+                        ParseErrorKind::Separator(' '),
+                        cursor.pos(),
+                    ));
+                } else {
+                    let parsed = time_of_day(&mut cursor, mode)?;
+                    cursor.skip_whitespace();
+                    parsed
+                }
+            }
+        };
+        cursor.finish()?;
+
+        if time >= DAY {
+            let ints = time.as_ticks() / (100 * 100);
+            return Err(out_of_range(Component::Interval, ints, 99, ints_at));
+        }
+        let start_of_day = SystemTime::from_c10_components(year, decaday, day, 0, 0, 0);
+        let result =
+            start_of_day.and_then(|start| start.checked_add(time).ok_or(RangeError::Timestamp));
+        result.map_err(|err| {
+            let position = match err {
+                RangeError::Component { component, .. } => match component {
+                    Component::Year => year_at,
+                    Component::Decaday => decaday_at,
+                    Component::Day => day_at,
+                    Component::Interval => ints_at,
+                    Component::Centival => cents_at,
+                    Component::Tick => ticks_at,
+                },
+                RangeError::Timestamp => year_at,
+            };
+            ParseError::new(ParseErrorKind::OutOfRange(err), position)
+        })
+    }
+}
+
+impl FromStr for SystemTime {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SystemTime::parse(s, Mode::Lenient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CENTIVAL, INTERVAL, TICK};
+
+    /// A small deterministic xorshift generator for property tests.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    fn kind<T: fmt::Debug>(result: Result<T, ParseError>) -> (ParseErrorKind, usize) {
+        let err = result.unwrap_err();
+        (err.kind(), err.position())
+    }
+
+    #[test]
+    fn durations() {
+        let expected = Duration::new(12, 34, 56);
+        assert_eq!(Duration::parse("12:34:56", Mode::Strict), Ok(expected));
+        assert_eq!("12:34:56".parse(), Ok(expected));
+        assert_eq!(" 12:34:56\n".parse(), Ok(expected));
+        assert_eq!("12:34:56.5".parse(), Ok(expected + TICK / 2));
+        assert_eq!("0:25:0".parse(), Ok(25 * CENTIVAL));
+        assert_eq!("150:00:00".parse(), Ok(150 * INTERVAL));
+        assert_eq!(
+            "00:00:00.1234567891".parse(),
+            Ok(Duration::from_nanoticks(123_456_789))
+        );
+    }
+
+    #[test]
+    fn duration_errors() {
+        let strict = |s| kind(Duration::parse(s, Mode::Strict));
+        let lenient = |s| kind(Duration::parse(s, Mode::Lenient));
+
+        assert_eq!(
+            strict("0:25:00"),
+            (ParseErrorKind::Invalid(Component::Interval), 0)
+        );
+        assert_eq!(
+            strict("00:25:0"),
+            (ParseErrorKind::Invalid(Component::Tick), 6)
+        );
+        assert_eq!(
+            strict(" 00:25:00"),
+            (ParseErrorKind::Invalid(Component::Interval), 0)
+        );
+        assert_eq!(
+            strict("00:00:00.1234567891"),
+            (ParseErrorKind::InvalidSubtick, 9)
+        );
+        assert_eq!(strict("00:00:00."), (ParseErrorKind::InvalidSubtick, 9));
+        assert_eq!(lenient("00-25:00"), (ParseErrorKind::Separator(':'), 2));
+        assert_eq!(lenient("00:25:00 x"), (ParseErrorKind::Trailing, 9));
+        assert_eq!(
+            lenient("00::00"),
+            (ParseErrorKind::Invalid(Component::Centival), 3)
+        );
+        assert_eq!(
+            lenient(""),
+            (ParseErrorKind::Invalid(Component::Interval), 0)
+        );
+
+        // a third digit is read so that the error points at the field, not past it
+        let tick_range = RangeError::Component {
+            component: Component::Tick,
+            value: 100,
+            min: 0,
+            max: 99,
+        };
+        assert_eq!(
+            lenient("00:99:100"),
+            (ParseErrorKind::OutOfRange(tick_range), 6)
+        );
+        assert_eq!(
+            strict("00:100:00").0,
+            ParseErrorKind::OutOfRange(RangeError::Component {
+                component: Component::Centival,
+                value: 100,
+                min: 0,
+                max: 99,
+            })
+        );
+        assert_eq!(
+            strict("00:25:005"),
+            (ParseErrorKind::Invalid(Component::Tick), 6)
+        );
+        assert_eq!(lenient("00:25:0000"), (ParseErrorKind::Trailing, 9));
+        assert_eq!(
+            lenient("1844674407370956:00:00").0,
+            ParseErrorKind::OutOfRange(RangeError::Component {
+                component: Component::Interval,
+                value: 1_844_674_407_370_956,
+                min: 0,
+                max: 1_844_674_407_370_955,
+            })
+        );
+    }
+
+    #[test]
+    fn times() {
+        let expected = SystemTime::from_c10_components(2023, 4, 7, 12, 34, 56).unwrap();
+        assert_eq!(
+            SystemTime::parse("2023  4.07 12:34:56", Mode::Strict),
+            Ok(expected)
+        );
+        assert_eq!("2023 4.7 12:34:56".parse(), Ok(expected));
+        assert_eq!("  2023\t4.07   12:34:56  ".parse(), Ok(expected));
+        assert_eq!(
+            "2023 4.07".parse(),
+            Ok(SystemTime::from_c10_components(2023, 4, 7, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            SystemTime::parse(" -44  8.05 00:00:00", Mode::Strict),
+            SystemTime::from_c10_components(-44, 8, 5, 0, 0, 0).map_err(|_| unreachable!())
+        );
+    }
+
+    #[test]
+    fn time_errors() {
+        let strict = |s| kind(SystemTime::parse(s, Mode::Strict));
+        let lenient = |s| kind(SystemTime::parse(s, Mode::Lenient));
+
+        assert_eq!(
+            strict("2023 4.07 12:34:56"),
+            (ParseErrorKind::Invalid(Component::Decaday), 5)
+        );
+        assert_eq!(
+            strict("2023  4.7 12:34:56"),
+            (ParseErrorKind::Invalid(Component::Day), 8)
+        );
+        assert_eq!(
+            strict(" 2023  4.07 12:34:56"),
+            (ParseErrorKind::Invalid(Component::Year), 0)
+        );
+        assert_eq!(strict("2023  4.07"), (ParseErrorKind::Separator(' '), 10));
+        assert_eq!(lenient("20234.07"), (ParseErrorKind::Separator(' '), 5));
+        assert_eq!(
+            lenient("2023 4.0712:00:00"),
+            (ParseErrorKind::Separator(' '), 9)
+        );
+
+        let range = |component, value, min, max| {
+            ParseErrorKind::OutOfRange(RangeError::Component {
+                component,
+                value,
+                min,
+                max,
+            })
+        };
+        assert_eq!(lenient("2023 37.6"), (range(Component::Day, 6, 1, 5), 8));
+        assert_eq!(
+            lenient("2023 38.01"),
+            (range(Component::Decaday, 38, 1, 37), 5)
+        );
+        assert_eq!(
+            lenient("2023 1.01 100:00:00"),
+            (range(Component::Interval, 100, 0, 99), 10)
+        );
+        assert_eq!(
+            lenient("2023 1.01 00:00:100"),
+            (range(Component::Tick, 100, 0, 99), 16)
+        );
+        assert_eq!(
+            lenient("99999999999999999999 1.01"),
+            (ParseErrorKind::Invalid(Component::Year), 0)
+        );
+    }
+
+    #[test]
+    fn round_trip_durations() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..10_000 {
+            let duration = Duration::from_nanoticks((rng.next() % 1_000_000_000_000_000) as u128);
+            let whole = Duration::from_ticks(duration.as_ticks());
+
+            let displayed = duration.to_string();
+            assert_eq!(Duration::parse(&displayed, Mode::Strict), Ok(whole));
+            assert_eq!(displayed.parse(), Ok(whole));
+
+            let precise = format!("{duration:.9}");
+            assert_eq!(Duration::parse(&precise, Mode::Strict), Ok(duration));
+            assert_eq!(precise.parse(), Ok(duration));
+        }
+    }
+
+    #[test]
+    fn round_trip_times() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..10_000 {
+            // times within roughly +/- 30,000 years of the epoch, to the nanotick
+            let nanoticks = (rng.next() % (1 << 62)) as i128 - (1 << 61);
+            let time = SystemTime::from_nanoticks(nanoticks * 1000 + 999).unwrap();
+            let whole = SystemTime::from_ticks(time.as_ticks()).unwrap();
+
+            let displayed = time.to_string();
+            assert_eq!(SystemTime::parse(&displayed, Mode::Strict), Ok(whole));
+            assert_eq!(displayed.parse(), Ok(whole));
+
+            let precise = format!("{time:.9}");
+            assert_eq!(SystemTime::parse(&precise, Mode::Strict), Ok(time));
+            assert_eq!(precise.parse(), Ok(time));
+        }
+    }
+}
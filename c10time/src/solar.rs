@@ -0,0 +1,369 @@
+//! Solar time, and the times of sunrise, solar noon and sunset.
+//!
+//! Mean solar time runs exactly one day per mean solar day, shifted from UTC by longitude: one
+//! degree east is 1/360 of a day, or 2,777.7… ticks. Apparent solar time follows the sun itself,
+//! which over the year runs as much as 16 minutes (1.1 intervals) ahead of mean time and 14
+//! minutes (about an interval) behind it; the difference is the equation of time. On either
+//! clock, noon falls at `50:00:00`.
+//!
+//! The sun's position is computed with the low-precision algorithms from Jean Meeus's
+//! *Astronomical Algorithms* used by NOAA's solar calculator, which are good to about a minute for
+//! sunrise and sunset between 1800 and 2100 (away from the poles).
+//!
+//! ```
+//! use c10::solar::Observer;
+//! use c10::SystemTime;
+//!
+//! // Greenwich, when the sun runs furthest behind the mean
+//! let time = SystemTime::from_unix_seconds(1_676_116_800).unwrap(); // 2023-02-11 12:00 UTC
+//! assert_eq!(time.to_mean_solar(0.0).time_components(), (50, 0, 0));
+//! assert_eq!(time.to_apparent_solar(0.0).time_components(), (49, 1, 16));
+//!
+//! let greenwich = Observer::new(51.4769, 0.0).unwrap();
+//! let noon = greenwich.solar_noon(time);
+//! assert_eq!(noon.time_components(), (50, 98, 83));
+//! ```
+
+use std::f64::consts::PI;
+
+use crate::SystemTime;
+
+/// Ticks in a day.
+const TICKS_PER_DAY: f64 = 1_000_000.0;
+
+/// The Julian date of the Unix epoch.
+const JULIAN_UNIX_EPOCH: f64 = 2_440_587.5;
+
+/// The Julian date of the J2000.0 epoch, from which Julian centuries are counted.
+const JULIAN_J2000: f64 = 2_451_545.0;
+
+/// How far below the horizon the center of the sun lies at sunrise and sunset, in degrees,
+/// allowing for refraction and the size of the sun's disk.
+const SUNRISE_DEPRESSION: f64 = 0.833;
+
+/// A place on Earth from which to watch the sun.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Observer {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl Observer {
+    /// Returns an observer at `latitude` degrees north (negative for south) and `longitude`
+    /// degrees east (negative for west), or `None` if either lies outside its range.
+    pub fn new(latitude: f64, longitude: f64) -> Option<Observer> {
+        let valid = (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude);
+        valid.then_some(Observer {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Returns the observer's latitude in degrees north.
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    /// Returns the observer's longitude in degrees east.
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// Returns when the sun crosses the meridian on the observer's day holding `time`.
+    ///
+    /// Days here are mean solar days at the observer's longitude, running from one mean midnight
+    /// to the next.
+    pub fn solar_noon(&self, time: SystemTime) -> SystemTime {
+        let noon = self.event(time, |_| Some(0.0));
+        noon.unwrap_or(time)
+    }
+
+    /// Returns when the top of the sun rises on the observer's day holding `time`, or `None` if
+    /// the sun stays above or below the horizon all day.
+    pub fn sunrise(&self, time: SystemTime) -> Option<SystemTime> {
+        self.event(time, |declination| Some(-self.hour_angle(declination)?))
+    }
+
+    /// Returns when the top of the sun sets on the observer's day holding `time`, or `None` if
+    /// the sun stays above or below the horizon all day.
+    pub fn sunset(&self, time: SystemTime) -> Option<SystemTime> {
+        self.event(time, |declination| self.hour_angle(declination))
+    }
+
+    // finds the time the sun reaches the hour angle (in degrees) given by `hour_angle` for its
+    // declination, refining it with the sun's position at each estimate
+    fn event(
+        &self,
+        time: SystemTime,
+        hour_angle: impl Fn(f64) -> Option<f64>,
+    ) -> Option<SystemTime> {
+        let shift = self.longitude / 360.0;
+        let mean_noon = (days(time) + shift).floor() + 0.5 - shift;
+        let mut estimate = mean_noon;
+        for _ in 0..3 {
+            let sun = Sun::at(estimate);
+            let equation = sun.equation_of_time / TICKS_PER_DAY;
+            estimate = mean_noon - equation + hour_angle(sun.declination)? / 360.0;
+        }
+        from_days(estimate)
+    }
+
+    // the hour angle of sunset for the sun at `declination`, if it sets
+    fn hour_angle(&self, declination: f64) -> Option<f64> {
+        let (latitude, declination) = (self.latitude.to_radians(), declination.to_radians());
+        let cos = ((90.0 + SUNRISE_DEPRESSION).to_radians().cos()
+            - latitude.sin() * declination.sin())
+            / (latitude.cos() * declination.cos());
+        (-1.0..=1.0).contains(&cos).then(|| cos.acos().to_degrees())
+    }
+}
+
+impl SystemTime {
+    /// Returns this time as read on a mean solar clock at `longitude` degrees east.
+    pub fn to_mean_solar(&self, longitude: f64) -> SystemTime {
+        self.shifted_ticks(longitude / 360.0 * TICKS_PER_DAY)
+    }
+
+    /// Returns this time as read on a sundial at `longitude` degrees east: mean solar time
+    /// corrected by the [`equation_of_time`].
+    pub fn to_apparent_solar(&self, longitude: f64) -> SystemTime {
+        let shift = longitude / 360.0 * TICKS_PER_DAY + equation_of_time(*self);
+        self.shifted_ticks(shift)
+    }
+
+    // moves the time by a fractional number of ticks, or not at all by an infinite or NaN one
+    fn shifted_ticks(&self, ticks: f64) -> SystemTime {
+        if !ticks.is_finite() {
+            return *self;
+        }
+        self.shifted_nanoticks((ticks * 1e9).round() as i128)
+    }
+}
+
+/// Returns how far apparent solar time runs ahead of mean solar time at `time`, in ticks
+/// (negative when it runs behind).
+///
+/// Over the year this ranges from about −9,860 ticks (−14 minutes) in February to about +11,390
+/// ticks (+16 minutes) in November.
+pub fn equation_of_time(time: SystemTime) -> f64 {
+    Sun::at(days(time)).equation_of_time
+}
+
+/// Returns the sun's declination at `time`: how far north of the celestial equator it lies, in
+/// degrees.
+pub fn declination(time: SystemTime) -> f64 {
+    Sun::at(days(time)).declination
+}
+
+/// The parts of the sun's position that decide solar time and the length of the day.
+struct Sun {
+    // degrees
+    declination: f64,
+    // ticks
+    equation_of_time: f64,
+}
+
+impl Sun {
+    // computes the sun's position `days` after the Unix epoch, following NOAA's solar calculator
+    fn at(days: f64) -> Sun {
+        let t = (days + JULIAN_UNIX_EPOCH - JULIAN_J2000) / 36_525.0;
+
+        // geometric mean longitude and anomaly of the sun, and the eccentricity of Earth's orbit
+        let longitude = (280.466_46 + t * (36_000.769_83 + t * 0.000_303_2)).rem_euclid(360.0);
+        let anomaly = 357.529_11 + t * (35_999.050_29 - 0.000_153_7 * t);
+        let eccentricity = 0.016_708_634 - t * (0.000_042_037 + 0.000_000_126_7 * t);
+
+        let m = anomaly.to_radians();
+        let center = m.sin() * (1.914_602 - t * (0.004_817 + 0.000_014 * t))
+            + (2.0 * m).sin() * (0.019_993 - 0.000_101 * t)
+            + (3.0 * m).sin() * 0.000_289;
+        let omega = (125.04 - 1_934.136 * t).to_radians();
+        let apparent = (longitude + center - 0.005_69 - 0.004_78 * omega.sin()).to_radians();
+
+        let seconds = 21.448 - t * (46.815 + t * (0.000_59 - t * 0.001_813));
+        let mean_obliquity = 23.0 + (26.0 + seconds / 60.0) / 60.0;
+        let obliquity = (mean_obliquity + 0.002_56 * omega.cos()).to_radians();
+        let declination = (obliquity.sin() * apparent.sin()).asin().to_degrees();
+
+        let y = (obliquity / 2.0).tan().powi(2);
+        let (l, e) = (longitude.to_radians(), eccentricity);
+        let equation = y * (2.0 * l).sin() - 2.0 * e * m.sin()
+            + 4.0 * e * y * m.sin() * (2.0 * l).cos()
+            - 0.5 * y * y * (4.0 * l).sin()
+            - 1.25 * e * e * (2.0 * m).sin();
+
+        Sun {
+            declination,
+            // radians of the sun's hour angle, as a fraction of a day
+            equation_of_time: equation / (2.0 * PI) * TICKS_PER_DAY,
+        }
+    }
+}
+
+/// Returns the days since the Unix epoch at `time`, with the time of day as a fraction.
+fn days(time: SystemTime) -> f64 {
+    time.as_nanoticks() as f64 / 1e9 / TICKS_PER_DAY
+}
+
+/// Returns the time `days` after the Unix epoch, if representable.
+fn from_days(days: f64) -> Option<SystemTime> {
+    let nanoticks = days * TICKS_PER_DAY * 1e9;
+    SystemTime::from_nanoticks(nanoticks.round() as i128).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gregorian::utc;
+
+    // asserts that `time` lies within `seconds` of `expected`
+    fn near(time: Option<SystemTime>, expected: SystemTime, seconds: f64) {
+        let time = time.expect("no event");
+        let apart = (time.as_nanoticks() - expected.as_nanoticks()) as f64 / 1e9 * 0.0864;
+        assert!(
+            apart.abs() <= seconds,
+            "{} is {apart:.0} s from {}",
+            time.to_gregorian(),
+            expected.to_gregorian()
+        );
+    }
+
+    // the equation of time in minutes
+    fn minutes(time: SystemTime) -> f64 {
+        equation_of_time(time) * 0.0864 / 60.0
+    }
+
+    #[test]
+    fn mean_solar_time() {
+        let noon = utc(2023, 6, 1, 12, 0, 0);
+        assert_eq!(noon.to_mean_solar(0.0), noon);
+        assert_eq!(noon.to_mean_solar(90.0).time_components(), (75, 0, 0));
+        assert_eq!(noon.to_mean_solar(-180.0).time_components(), (0, 0, 0));
+        assert_eq!(
+            noon.to_mean_solar(-180.0).date_components(),
+            noon.date_components()
+        );
+        // a degree of longitude is 2,777.7… ticks
+        assert_eq!(noon.to_mean_solar(1.0).time_components(), (50, 27, 77));
+
+        // shifts that cannot be represented leave the time as it is
+        let first = SystemTime::from_ticks(1).unwrap();
+        for longitude in [
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+            f64::MAX,
+            -f64::MAX,
+        ] {
+            assert_eq!(first.to_mean_solar(longitude), first);
+            assert_eq!(first.to_apparent_solar(longitude), first);
+        }
+        assert_eq!(SystemTime::MAX.to_mean_solar(1.0), SystemTime::MAX);
+        assert_eq!(SystemTime::MIN.to_mean_solar(-1.0), SystemTime::MIN);
+    }
+
+    #[test]
+    fn equation_of_time_extremes() {
+        // the turning points of the equation of time, as tabulated for sundials
+        for (month, day, expected) in [(2, 11, -14.2), (5, 14, 3.7), (7, 26, -6.5), (11, 3, 16.4)] {
+            let equation = minutes(utc(2023, month, day, 12, 0, 0));
+            assert!(
+                (equation - expected).abs() < 0.1,
+                "{month}-{day}: {equation}"
+            );
+        }
+        // and its zeros, near which apparent and mean time agree
+        for (month, day) in [(4, 15), (6, 13), (9, 1), (12, 25)] {
+            assert!(
+                minutes(utc(2023, month, day, 12, 0, 0)).abs() < 0.3,
+                "{month}-{day}"
+            );
+        }
+
+        // the same extremes in ticks
+        assert!((equation_of_time(utc(2023, 2, 11, 12, 0, 0)) + 9_860.0).abs() < 100.0);
+        assert!((equation_of_time(utc(2023, 11, 3, 12, 0, 0)) - 11_390.0).abs() < 100.0);
+
+        let time = utc(2023, 11, 3, 12, 0, 0);
+        let ahead = time.to_apparent_solar(0.0).duration_since(time).unwrap();
+        assert_eq!(ahead.as_ticks(), equation_of_time(time) as u64);
+    }
+
+    #[test]
+    fn declination_at_solstices_and_equinoxes() {
+        assert!((declination(utc(2023, 6, 21, 14, 58, 0)) - 23.44).abs() < 0.01);
+        assert!((declination(utc(2023, 12, 22, 3, 27, 0)) + 23.44).abs() < 0.01);
+        assert!(declination(utc(2023, 3, 20, 21, 24, 0)).abs() < 0.01);
+        assert!(declination(utc(2023, 9, 23, 6, 50, 0)).abs() < 0.01);
+    }
+
+    #[test]
+    fn published_times() {
+        // sunrise, solar noon and sunset in UTC, as published to the minute
+        let london = Observer::new(51.5074, -0.1278).unwrap();
+        let day = utc(2023, 6, 21, 12, 0, 0);
+        near(london.sunrise(day), utc(2023, 6, 21, 3, 43, 0), 60.0);
+        near(
+            Some(london.solar_noon(day)),
+            utc(2023, 6, 21, 12, 2, 0),
+            60.0,
+        );
+        near(london.sunset(day), utc(2023, 6, 21, 20, 21, 0), 60.0);
+
+        let new_york = Observer::new(40.7128, -74.006).unwrap();
+        let day = utc(2023, 12, 21, 17, 0, 0);
+        near(new_york.sunrise(day), utc(2023, 12, 21, 12, 16, 0), 60.0);
+        near(new_york.sunset(day), utc(2023, 12, 21, 21, 32, 0), 60.0);
+
+        // the day in Sydney begins the evening before in UTC
+        let sydney = Observer::new(-33.8688, 151.2093).unwrap();
+        let day = utc(2023, 12, 22, 2, 0, 0);
+        near(sydney.sunrise(day), utc(2023, 12, 21, 18, 41, 0), 60.0);
+        near(sydney.sunset(day), utc(2023, 12, 22, 9, 5, 0), 60.0);
+
+        // at the equator, day and night are almost equal even at the solstices
+        let equator = Observer::new(0.0, 0.0).unwrap();
+        let day = utc(2023, 3, 21, 12, 0, 0);
+        let rise = equator.sunrise(day).unwrap();
+        let length = equator.sunset(day).unwrap().duration_since(rise).unwrap();
+        assert!((length.as_ticks() as f64 * 0.0864 / 60.0 - 727.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn noon_follows_the_equation_of_time() {
+        // the sun crosses the Greenwich meridian late in February and early in November
+        let greenwich = Observer::new(51.4769, 0.0).unwrap();
+        near(
+            Some(greenwich.solar_noon(utc(2023, 2, 11, 0, 0, 0))),
+            utc(2023, 2, 11, 12, 14, 0),
+            30.0,
+        );
+        near(
+            Some(greenwich.solar_noon(utc(2023, 11, 3, 23, 59, 0))),
+            utc(2023, 11, 3, 11, 44, 0),
+            30.0,
+        );
+        // which is noon by the sundial
+        let noon = greenwich.solar_noon(utc(2023, 11, 3, 12, 0, 0));
+        let (ints, cents, _) = noon.to_apparent_solar(0.0).time_components();
+        assert!((ints, cents) == (50, 0) || (ints, cents) == (49, 99));
+    }
+
+    #[test]
+    fn polar_days_and_nights() {
+        let tromso = Observer::new(69.6492, 18.9553).unwrap();
+        assert_eq!(tromso.sunset(utc(2023, 6, 21, 12, 0, 0)), None);
+        assert_eq!(tromso.sunrise(utc(2023, 12, 21, 12, 0, 0)), None);
+        assert!(tromso.sunrise(utc(2023, 3, 21, 12, 0, 0)).is_some());
+        // noon comes whether or not the sun rises
+        near(
+            Some(tromso.solar_noon(utc(2023, 12, 21, 12, 0, 0))),
+            utc(2023, 12, 21, 10, 42, 0),
+            60.0,
+        );
+
+        assert!(Observer::new(90.1, 0.0).is_none());
+        assert!(Observer::new(0.0, -180.5).is_none());
+        assert!(Observer::new(f64::NAN, 0.0).is_none());
+    }
+}
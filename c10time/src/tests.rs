@@ -18,5 +18,477 @@ fn one_day() {
 #[test]
 fn now() {
     let now = SystemTime::now();
-    assert_ne!(now, SystemTime { ticks: 0 });
+    assert_ne!(now, SystemTime::UNIX_EPOCH);
+}
+
+#[test]
+fn duration_accessors() {
+    assert_eq!(Duration::from_ticks(12_345).as_ticks(), 12_345);
+    assert_eq!(Duration::new(1, 23, 45), Duration::from_ticks(12_345));
+    assert!(Duration::ZERO.is_zero());
+    assert!(!TICK.is_zero());
+}
+
+#[test]
+fn duration_operators() {
+    let mut d = INTERVAL + CENTIVAL;
+    assert_eq!(d, Duration::new(1, 1, 0));
+    d -= CENTIVAL;
+    assert_eq!(d, INTERVAL);
+    d += TICK;
+    assert_eq!(d - TICK, INTERVAL);
+
+    assert_eq!(INTERVAL * 100, DAY);
+    assert_eq!(10 * DAY, DECADAY);
+    assert_eq!(DAY / 100, INTERVAL);
+    assert_eq!(Duration::new(1, 2, 3) % CENTIVAL, Duration::new(0, 0, 3));
+
+    let mut d = CENTIVAL;
+    d *= 3;
+    d /= 2;
+    d %= CENTIVAL;
+    assert_eq!(d, Duration::new(0, 0, 50));
+}
+
+#[test]
+fn duration_sum() {
+    let parts = [INTERVAL, CENTIVAL, TICK];
+    assert_eq!(parts.iter().sum::<Duration>(), Duration::new(1, 1, 1));
+    assert_eq!(parts.into_iter().sum::<Duration>(), Duration::new(1, 1, 1));
+    assert_eq!(
+        std::iter::empty::<Duration>().sum::<Duration>(),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn duration_checked() {
+    assert_eq!(Duration::MAX.checked_add(TICK), None);
+    assert_eq!(TICK.checked_sub(CENTIVAL), None);
+    assert_eq!(Duration::MAX.checked_mul(2), None);
+    assert_eq!(DAY.checked_div(0), None);
+    assert_eq!(DAY.checked_rem(Duration::ZERO), None);
+    assert_eq!(CENTIVAL.checked_sub(TICK), Some(Duration::new(0, 0, 99)));
+    assert_eq!(DAY.checked_div(10), Some(Duration::new(10, 0, 0)));
+}
+
+#[test]
+fn duration_saturating_and_wrapping() {
+    assert_eq!(Duration::MAX.saturating_add(TICK), Duration::MAX);
+    assert_eq!(TICK.saturating_sub(CENTIVAL), Duration::ZERO);
+    assert_eq!(Duration::MAX.saturating_mul(2), Duration::MAX);
+    let max_ticks = Duration::from_ticks(u64::MAX);
+    assert_eq!(max_ticks.wrapping_add(TICK), Duration::ZERO);
+    assert_eq!(Duration::ZERO.wrapping_sub(TICK), max_ticks);
+    assert_eq!(max_ticks.wrapping_mul(2), max_ticks - TICK);
+}
+
+#[test]
+#[should_panic(expected = "overflow when adding durations")]
+fn duration_add_overflow() {
+    let _ = Duration::MAX + TICK;
+}
+
+#[test]
+#[should_panic(expected = "overflow when subtracting durations")]
+fn duration_sub_overflow() {
+    let _ = TICK - CENTIVAL;
+}
+
+#[test]
+#[should_panic(expected = "divide by zero")]
+fn duration_div_by_zero() {
+    let _ = DAY / 0;
+}
+
+#[test]
+fn system_time_duration_arithmetic() {
+    let start = SystemTime::UNIX_EPOCH + DECADAY;
+    let mut deadline = start + INTERVAL;
+    assert_eq!(deadline - start, INTERVAL);
+    assert_eq!(deadline - INTERVAL, start);
+
+    deadline += CENTIVAL;
+    deadline -= TICK;
+    assert_eq!(deadline.duration_since(start), Ok(Duration::new(1, 0, 99)));
+}
+
+#[test]
+fn system_time_duration_since_later() {
+    let earlier = SystemTime::UNIX_EPOCH + DAY;
+    let later = earlier + CENTIVAL;
+    let err = earlier.duration_since(later).unwrap_err();
+    assert_eq!(err.duration(), CENTIVAL);
+}
+
+#[test]
+fn system_time_checked() {
+    assert_eq!(
+        SystemTime::UNIX_EPOCH.checked_sub(TICK),
+        SystemTime::from_ticks(-1).ok()
+    );
+    assert_eq!(
+        SystemTime::UNIX_EPOCH.checked_add(DAY),
+        SystemTime::from_ticks(1_000_000).ok()
+    );
+    assert_eq!(SystemTime::MAX.checked_add(TICK), None);
+    assert_eq!(SystemTime::MIN.checked_sub(TICK), None);
+    assert_eq!(
+        SystemTime::MIN.duration_since(SystemTime::MAX).unwrap_err(),
+        SystemTimeError(SystemTime::MAX - SystemTime::MIN)
+    );
+}
+
+#[test]
+fn system_time_elapsed() {
+    let then = SystemTime::now() - CENTIVAL;
+    assert!(then.elapsed().unwrap() >= CENTIVAL);
+    assert!((SystemTime::now() + DAY).elapsed().is_err());
+}
+
+#[test]
+#[should_panic(expected = "later than self")]
+fn system_time_sub_later() {
+    let _ = SystemTime::UNIX_EPOCH - (SystemTime::UNIX_EPOCH + TICK);
+}
+
+#[test]
+fn before_epoch_components() {
+    let last_tick_1969 = SystemTime::UNIX_EPOCH - TICK;
+    assert_eq!(last_tick_1969.date_components(), (1969, 37, 5));
+    assert_eq!(last_tick_1969.time_components(), (99, 99, 99));
+    assert_eq!(last_tick_1969.to_string(), "1969 37.05 99:99:99");
+    assert_eq!(SystemTime::UNIX_EPOCH.to_string(), "1970  1.01 00:00:00");
+
+    let ticks_1900 = epochs::year_to_ticks(1900);
+    let noon_1900 = SystemTime::from_ticks(ticks_1900).unwrap() + 50 * INTERVAL;
+    assert_eq!(noon_1900.date_components(), (1900, 1, 1));
+    assert_eq!(noon_1900.time_components(), (50, 0, 0));
+
+    let year_one = SystemTime::from_ticks(epochs::year_to_ticks(1)).unwrap();
+    assert_eq!(year_one.date_components(), (1, 1, 1));
+    assert_eq!((year_one - TICK).date_components(), (0, 37, 6));
+}
+
+#[test]
+fn extreme_components() {
+    let (year, decaday, day) = SystemTime::MIN.date_components();
+    assert_eq!((decaday, day), (1, 1));
+    assert_eq!(SystemTime::MIN.time_components(), (0, 0, 0));
+    assert!(year < -25_000_000_000);
+
+    let (year, _, _) = SystemTime::MAX.date_components();
+    assert!(year > 25_000_000_000);
+}
+
+#[test]
+fn from_ticks() {
+    assert_eq!(SystemTime::from_ticks(-1).unwrap().as_ticks(), -1);
+    assert_eq!(
+        SystemTime::from_ticks(i64::MAX).map(|t| t.as_ticks()),
+        Ok(i64::MAX)
+    );
+    assert_eq!(SystemTime::from_ticks(i64::MIN), Err(RangeError::Timestamp));
+}
+
+#[test]
+fn from_unix() {
+    let new_year_2023 = SystemTime::from_c10_components(2023, 1, 1, 0, 0, 0).unwrap();
+    assert_eq!(SystemTime::from_unix_seconds(1672531200), Ok(new_year_2023));
+    assert_eq!(
+        SystemTime::from_unix_nanos(1_672_531_200_086_400_000),
+        Ok(new_year_2023 + TICK)
+    );
+    assert_eq!(
+        SystemTime::from_unix_nanos(1_672_531_200_086_399_999).map(|t| t.as_ticks()),
+        Ok(new_year_2023.as_ticks())
+    );
+    assert_eq!(
+        SystemTime::from_unix_nanos(-1).map(|t| t.as_ticks()),
+        Ok(-1)
+    );
+    assert_eq!(
+        SystemTime::from_unix_nanos(i128::MAX),
+        Err(RangeError::Timestamp)
+    );
+}
+
+#[test]
+fn from_c10_components_round_trip() {
+    for (year, decaday, day, ints, cents, ticks) in [
+        (1970, 1, 1, 0, 0, 0),
+        (1969, 37, 5, 99, 99, 99),
+        (2024, 37, 6, 12, 34, 56),
+        (1900, 18, 3, 50, 0, 0),
+        (1, 1, 1, 0, 0, 1),
+        (-44, 8, 5, 0, 0, 0),
+    ] {
+        let t = SystemTime::from_c10_components(year, decaday, day, ints, cents, ticks).unwrap();
+        assert_eq!(t.date_components(), (year, decaday, day));
+        assert_eq!(t.time_components(), (ints, cents, ticks));
+    }
+}
+
+#[test]
+fn from_c10_components_invalid() {
+    let err = |component, value, min, max| {
+        Err(RangeError::Component {
+            component,
+            value,
+            min,
+            max,
+        })
+    };
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 0, 1, 0, 0, 0),
+        err(Component::Decaday, 0, 1, 37)
+    );
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 38, 1, 0, 0, 0),
+        err(Component::Decaday, 38, 1, 37)
+    );
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 37, 6, 0, 0, 0),
+        err(Component::Day, 6, 1, 5)
+    );
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 3, 11, 0, 0, 0),
+        err(Component::Day, 11, 1, 10)
+    );
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 3, 1, 100, 0, 0),
+        err(Component::Interval, 100, 0, 99)
+    );
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 3, 1, 0, 100, 0),
+        err(Component::Centival, 100, 0, 99)
+    );
+    assert_eq!(
+        SystemTime::from_c10_components(2023, 3, 1, 0, 0, 100),
+        err(Component::Tick, 100, 0, 99)
+    );
+    assert!(matches!(
+        SystemTime::from_c10_components(i64::MAX, 1, 1, 0, 0, 0),
+        Err(RangeError::Component {
+            component: Component::Year,
+            ..
+        })
+    ));
+
+    let (max_year, _, _) = SystemTime::MAX.date_components();
+    assert_eq!(
+        SystemTime::from_c10_components(max_year, 37, 5, 99, 99, 99),
+        Err(RangeError::Timestamp)
+    );
+    assert_eq!(
+        RangeError::Component {
+            component: Component::Centival,
+            value: 100,
+            min: 0,
+            max: 99
+        }
+        .to_string(),
+        "centival 100 is outside of the range 0..=99"
+    );
+}
+
+#[test]
+fn std_system_time_round_trip() {
+    for ticks in [
+        -1_000_000_000_000,
+        -1,
+        0,
+        1,
+        1_672_531_200 * 625 / 54,
+        1 << 50,
+    ] {
+        let c10 = SystemTime::from_ticks(ticks).unwrap();
+        let std = std::time::SystemTime::try_from(c10).unwrap();
+        assert_eq!(SystemTime::try_from(std), Ok(c10));
+    }
+}
+
+#[test]
+fn std_system_time_rounding() {
+    use std::time::UNIX_EPOCH;
+
+    let tick = std::time::Duration::from_micros(86_400);
+    let nanosecond = std::time::Duration::from_nanos(1);
+
+    // sub-tick precision is kept to the nanotick, rounding toward the past
+    let almost = SystemTime::try_from(UNIX_EPOCH + tick - nanosecond).unwrap();
+    assert_eq!(almost.as_ticks(), 0);
+    assert_eq!(almost.subtick_nanoticks(), 999_999_988);
+    assert_eq!(
+        SystemTime::try_from(UNIX_EPOCH + tick),
+        Ok(SystemTime::UNIX_EPOCH + TICK)
+    );
+    let before = SystemTime::try_from(UNIX_EPOCH - nanosecond).unwrap();
+    assert_eq!(before.as_ticks(), -1);
+    assert_eq!(before.subtick_nanoticks(), 999_999_988);
+    assert_eq!(
+        SystemTime::try_from(UNIX_EPOCH - tick),
+        Ok(SystemTime::UNIX_EPOCH - TICK)
+    );
+
+    // whole ticks convert back to std exactly, and sub-ticks to the nearest nanosecond
+    assert_eq!(
+        std::time::SystemTime::try_from(SystemTime::UNIX_EPOCH - 3 * TICK),
+        Ok(UNIX_EPOCH - 3 * tick)
+    );
+    for nanos in [
+        -86_400_001_i64,
+        -1,
+        1,
+        53,
+        86_399_999,
+        1_672_531_200_123_456_789,
+    ] {
+        let std = UNIX_EPOCH + std::time::Duration::from_nanos(nanos.max(0) as u64)
+            - std::time::Duration::from_nanos((-nanos).max(0) as u64);
+        let c10 = SystemTime::try_from(std).unwrap();
+        assert_eq!(std::time::SystemTime::try_from(c10), Ok(std));
+    }
+}
+
+#[test]
+fn std_duration_round_trip() {
+    for ticks in [0, 1, 99, 625, 1_000_000, u64::MAX] {
+        let c10 = Duration::from_ticks(ticks);
+        let std = std::time::Duration::from(c10);
+        assert_eq!(Duration::try_from(std), Ok(c10));
+        assert_eq!(Duration::from_std(std, Rounding::Nearest), Ok(c10));
+        assert_eq!(Duration::from_std(std, Rounding::Ceil), Ok(c10));
+    }
+    assert_eq!(
+        std::time::Duration::from(CENTIVAL),
+        std::time::Duration::from_millis(8_640)
+    );
+}
+
+#[test]
+fn std_duration_rounding() {
+    let ms = std::time::Duration::from_millis;
+    let round = |d, r| Duration::from_std(d, r).unwrap().as_ticks();
+
+    assert_eq!(round(ms(43), Rounding::Floor), 0);
+    assert_eq!(round(ms(43), Rounding::Nearest), 0);
+    assert_eq!(round(ms(43), Rounding::Ceil), 1);
+
+    // exactly half a tick rounds up
+    let half = std::time::Duration::from_micros(43_200);
+    assert_eq!(round(half, Rounding::Floor), 0);
+    assert_eq!(round(half, Rounding::Nearest), 1);
+    assert_eq!(round(ms(130), Rounding::Nearest), 2);
+    assert_eq!(round(ms(129), Rounding::Nearest), 1);
+
+    assert!(Duration::from_std(std::time::Duration::MAX, Rounding::Floor).is_err());
+}
+
+#[test]
+fn std_duration_remainder() {
+    let (whole, rest) =
+        Duration::from_std_with_remainder(std::time::Duration::from_millis(100)).unwrap();
+    assert_eq!(whole, TICK);
+    assert_eq!(rest, std::time::Duration::from_micros(13_600));
+
+    // carrying the remainder forward means a thousand 100ms steps add up to exactly 100s
+    let step = std::time::Duration::from_millis(100);
+    let mut carry = std::time::Duration::ZERO;
+    let mut total = Duration::ZERO;
+    for _ in 0..1000 {
+        let (whole, rest) = Duration::from_std_with_remainder(step + carry).unwrap();
+        total += whole;
+        carry = rest;
+    }
+    let exact = std::time::Duration::from_secs(100);
+    assert_eq!(std::time::Duration::from(total) + carry, exact);
+    assert_eq!(total, Duration::from_std(exact, Rounding::Floor).unwrap());
+}
+
+#[test]
+fn subtick_durations() {
+    let third = TICK / 3;
+    assert_eq!(third.as_ticks(), 0);
+    assert_eq!(third.subtick_nanoticks(), 333_333_333);
+    assert_eq!(third.subtick_microticks(), 333_333);
+    assert_eq!(third * 3 + Duration::from_nanoticks(1), TICK);
+    assert_eq!((CENTIVAL / 7).as_nanoticks(), 14_285_714_285);
+    assert_eq!(Duration::from_nanoticks(2_500_000_000) % TICK, TICK / 2);
+    assert!(!Duration::from_nanoticks(1).is_zero());
+
+    assert_eq!(
+        Duration::MAX.wrapping_add(Duration::from_nanoticks(1)),
+        Duration::ZERO
+    );
+    assert_eq!(
+        Duration::ZERO.wrapping_sub(Duration::from_nanoticks(1)),
+        Duration::MAX
+    );
+    assert_eq!(
+        (Duration::MAX / 2).wrapping_mul(2),
+        Duration::MAX - Duration::from_nanoticks(1)
+    );
+}
+
+#[test]
+fn subtick_std_duration() {
+    for nanos in [1, 53, 54, 86_399_999, 86_400_001, 123_456_789_012] {
+        let std = std::time::Duration::from_nanos(nanos);
+        let c10 = Duration::try_from(std).unwrap();
+        assert_eq!(std::time::Duration::from(c10), std);
+    }
+    let c10 = Duration::try_from(std::time::Duration::from_millis(100)).unwrap();
+    assert_eq!(c10.as_ticks(), 1);
+    assert_eq!(c10.subtick_nanoticks(), 157_407_407);
+}
+
+#[test]
+fn subtick_time() {
+    let t = SystemTime::from_nanoticks(-1).unwrap();
+    assert_eq!(t.as_ticks(), -1);
+    assert_eq!(t.subtick_nanoticks(), 999_999_999);
+    assert_eq!(t.as_nanoticks(), -1);
+    assert_eq!(t + Duration::from_nanoticks(1), SystemTime::UNIX_EPOCH);
+    assert_eq!(SystemTime::UNIX_EPOCH - t, Duration::from_nanoticks(1));
+
+    let t = SystemTime::from_unix_nanos(100_000_000).unwrap();
+    assert_eq!(t.as_ticks(), 1);
+    assert_eq!(t.subtick_microticks(), 157_407);
+    assert_eq!(
+        SystemTime::from_nanoticks(i128::MIN),
+        Err(RangeError::Timestamp)
+    );
+}
+
+#[test]
+fn subtick_display() {
+    let d = Duration::new(1, 2, 3) + TICK / 8;
+    assert_eq!(d.to_string(), "01:02:03");
+    assert_eq!(format!("{d:.0}"), "01:02:03");
+    assert_eq!(format!("{d:.1}"), "01:02:03.1");
+    assert_eq!(format!("{d:.3}"), "01:02:03.125");
+    assert_eq!(format!("{d:.12}"), "01:02:03.125000000");
+
+    let t = SystemTime::UNIX_EPOCH - TICK / 4;
+    assert_eq!(t.to_string(), "1969 37.05 99:99:99");
+    assert_eq!(format!("{t:.2}"), "1969 37.05 99:99:99.75");
+}
+
+#[test]
+fn local_offset() {
+    let now = SystemTime::now();
+    let offset = now.local_offset();
+    assert!(offset.abs() <= 26 * 60 * 60);
+    // the same zone a `TimeZone` reads
+    if let Ok(zone) = crate::zone::TimeZone::local() {
+        assert_eq!(offset, i64::from(now.in_zone(&zone).offset()));
+    }
+
+    let local = now.to_local();
+    let shift = match local.duration_since(now) {
+        Ok(ahead) => ahead.as_nanoticks() as i128,
+        Err(behind) => -(behind.duration().as_nanoticks() as i128),
+    };
+    assert_eq!(shift, offset as i128 * 625_000_000_000 / 54);
 }
@@ -0,0 +1,775 @@
+//! Timescales that count leap seconds differently, for defining the C10 day against atomic time.
+//!
+//! [`SystemTime::now`] reads the system clock, which counts UTC the POSIX way: every day has
+//! exactly 86,400 seconds, so when a leap second is inserted the clock repeats a second, and
+//! C10 days drift against atomic time by one second per leap second. A [`SystemTime`] may instead
+//! be read on another [`Timescale`], counted from 1970-01-01 00:00:00 on that scale, so that its
+//! C10 days are 1,000,000 ticks of atomic time.
+//!
+//! Converting between timescales takes a [`LeapSeconds`] table, read from a `leap-seconds.list`
+//! file as published by the IERS and shipped with the IANA time zone database. A copy is bundled
+//! with this crate, but leap seconds are announced only months ahead, so prefer a newer list where
+//! one is installed. Each list expires, and conversions say when they went past that:
+//!
+//! ```
+//! use c10::timescale::{LeapHandling, LeapSeconds, Timescale};
+//! use c10::SystemTime;
+//!
+//! let leaps = LeapSeconds::system().unwrap_or_else(|_| LeapSeconds::bundled().clone());
+//!
+//! // the last second of 2016 happened twice on POSIX clocks, as 23:59:59 and as 23:59:60
+//! let utc = SystemTime::from_unix_seconds(1_483_228_799).unwrap();
+//! let tai = leaps.convert(utc, Timescale::Utc, Timescale::Tai).unwrap();
+//! assert_eq!(tai.leap, LeapHandling::Ambiguous);
+//! assert!(!tai.expired);
+//! // and on TAI it was 35 seconds into 2017
+//! assert_eq!(tai.time.to_string(), "2017  1.01 00:04:05");
+//! ```
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::{RangeError, SystemTime, NANOTICKS_PER_TICK};
+
+/// Seconds from the NTP epoch, 1900-01-01 00:00:00 UTC, to the Unix epoch.
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+/// How far GPS time runs behind TAI, in seconds.
+const GPS_TAI_OFFSET: i64 = 19;
+
+/// Nanoticks in a day.
+const DAY: i128 = 1_000_000 * NANOTICKS_PER_TICK as i128;
+
+/// A way of counting time, and so of saying when each C10 day begins.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Timescale {
+    /// Coordinated Universal Time as POSIX clocks count it: every day has 86,400 seconds, so an
+    /// inserted leap second repeats the last second of its day. [`SystemTime::now`] counts this.
+    Utc,
+    /// UTC with each leap second spread evenly over the 24 hours from the noon before it to the
+    /// noon after, as some NTP servers serve it. Every day has 86,400 seconds, but those in the
+    /// smear are slightly longer (or shorter) than SI seconds.
+    UtcSmeared,
+    /// International Atomic Time, which has no leap seconds.
+    Tai,
+    /// GPS time, which runs 19 seconds behind TAI. Like the others, it is counted here from
+    /// 1970-01-01 00:00:00 on its own scale, rather than from the GPS epoch.
+    Gps,
+}
+
+impl fmt::Display for Timescale {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        fmter.write_str(match self {
+            Timescale::Utc => "UTC",
+            Timescale::UtcSmeared => "smeared UTC",
+            Timescale::Tai => "TAI",
+            Timescale::Gps => "GPS",
+        })
+    }
+}
+
+/// How a conversion between timescales dealt with a nearby leap second.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LeapHandling {
+    /// No leap second affected the conversion.
+    None,
+    /// The time lay within the day over which a leap second is smeared, and was stretched or
+    /// squeezed accordingly.
+    Smeared,
+    /// The time lay in an inserted 61st second, which POSIX UTC cannot show, so it was given as a
+    /// repeat of the 60th.
+    Repeated,
+    /// The UTC time lay in the 60th second of a day ending in an inserted leap second, which
+    /// POSIX UTC shows twice; it was taken to be the first, before the leap second.
+    Ambiguous,
+    /// The UTC time lay in a second removed by a negative leap second, which never happened; it
+    /// was converted as if the second had not been removed.
+    Skipped,
+}
+
+/// A time converted to another timescale.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Conversion {
+    /// The converted time.
+    pub time: SystemTime,
+    /// How a leap second affected the conversion, if one did. Converting between smeared and
+    /// POSIX UTC, the handling on the POSIX side is given.
+    pub leap: LeapHandling,
+    /// Whether the conversion went past when the table [`expires`](LeapSeconds::expires), so
+    /// that a leap second announced since may be missing from it.
+    pub expired: bool,
+}
+
+/// A table of leap seconds: the difference between TAI and UTC, and when it changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeapSeconds {
+    // the Unix times in seconds from which each difference TAI − UTC, in seconds, applied
+    changes: Vec<(i64, i64)>,
+    updated: SystemTime,
+    expires: SystemTime,
+}
+
+impl LeapSeconds {
+    /// Returns the table bundled with this crate, from the `leap-seconds.list` file last updated
+    /// by the IERS on 2026-01-06 and expiring on 2026-12-28.
+    pub fn bundled() -> &'static LeapSeconds {
+        static BUNDLED: OnceLock<LeapSeconds> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            match LeapSeconds::parse(include_str!("../data/leap-seconds.list")) {
+                Ok(leaps) => leaps,
+                Err(err) => panic!("bundled leap-seconds.list is invalid: {err}"),
+            }
+        })
+    }
+
+    /// Reads the `leap-seconds.list` file installed with the time zone database, in the
+    /// directory named by the `TZDIR` environment variable or else in
+    /// [`ZONEINFO`](crate::zone::ZONEINFO).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid leap second list.
+    pub fn system() -> Result<LeapSeconds, LeapError> {
+        LeapSeconds::from_file(crate::zone::zoneinfo_dir().join("leap-seconds.list"))
+    }
+
+    /// Reads a file in the format of `leap-seconds.list`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid leap second list.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<LeapSeconds, LeapError> {
+        LeapSeconds::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parses a list in the format of `leap-seconds.list`.
+    ///
+    /// Each line holds an NTP timestamp (seconds since 1900) and the difference TAI − UTC in
+    /// seconds from then on. Comments start with `#`, except for the lines giving when the list
+    /// was last updated (`#$`), when it expires (`#@`) and its SHA-1 hash (`#h`). The hash is
+    /// optional, but checked when given.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeapError::Line`] for a malformed line, [`LeapError::Missing`] if the list has no
+    /// leap seconds or lacks its update or expiry time, and [`LeapError::Hash`] if it does not
+    /// match its hash.
+    pub fn parse(text: &str) -> Result<LeapSeconds, LeapError> {
+        let (mut updated, mut expires, mut hash) = (None, None, None);
+        let mut lines = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let number = number + 1;
+            let invalid = |reason| LeapError::Line(number, reason);
+            let time = |field: &str, reason| {
+                let ntp = field.trim().parse::<i64>().map_err(|_| invalid(reason))?;
+                Ok::<_, LeapError>((ntp, ntp_to_time(ntp).ok_or_else(|| invalid(reason))?.1))
+            };
+            if let Some(field) = line.strip_prefix("#$") {
+                updated = Some(time(field, "invalid update time")?);
+            } else if let Some(field) = line.strip_prefix("#@") {
+                expires = Some(time(field, "invalid expiry time")?);
+            } else if let Some(fields) = line.strip_prefix("#h") {
+                let words: Vec<_> = fields
+                    .split_whitespace()
+                    .map(|word| u32::from_str_radix(word, 16))
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid("invalid hash"))?;
+                hash = Some(<[u32; 5]>::try_from(words).map_err(|_| invalid("invalid hash"))?);
+            } else {
+                let data = line.split('#').next().unwrap_or_default();
+                let mut fields = data.split_whitespace().map(str::parse::<i64>);
+                match (fields.next(), fields.next(), fields.next()) {
+                    (None, ..) => continue,
+                    (Some(Ok(ntp)), Some(Ok(offset)), None) => {
+                        let (unix, _) =
+                            ntp_to_time(ntp).ok_or_else(|| invalid("invalid NTP timestamp"))?;
+                        lines.push((number, ntp, unix, offset));
+                    }
+                    _ => return Err(invalid("expected an NTP timestamp and a TAI − UTC offset")),
+                }
+            }
+        }
+
+        let updated = updated.ok_or(LeapError::Missing("update time"))?;
+        let expires = expires.ok_or(LeapError::Missing("expiry time"))?;
+        if lines.is_empty() {
+            return Err(LeapError::Missing("leap seconds"));
+        }
+        if let Some(hash) = hash {
+            let mut digits = format!("{}{}", updated.0, expires.0);
+            for (_, ntp, _, offset) in &lines {
+                digits.push_str(&format!("{ntp}{offset}"));
+            }
+            if sha1(digits.as_bytes()) != hash {
+                return Err(LeapError::Hash);
+            }
+        }
+
+        let mut changes: Vec<(i64, i64)> = Vec::new();
+        for (number, _, time, offset) in lines {
+            if let Some(&(last, previous)) = changes.last() {
+                if time <= last {
+                    return Err(LeapError::Line(number, "leap seconds out of order"));
+                }
+                if offset.abs_diff(previous) != 1 {
+                    return Err(LeapError::Line(
+                        number,
+                        "offset does not change by one second",
+                    ));
+                }
+            }
+            changes.push((time, offset));
+        }
+        Ok(LeapSeconds {
+            changes,
+            updated: updated.1,
+            expires: expires.1,
+        })
+    }
+
+    /// Returns when the list was last updated.
+    pub fn updated(&self) -> SystemTime {
+        self.updated
+    }
+
+    /// Returns when the list expires: until then, it holds every leap second. Conversions after
+    /// it assume there are no more.
+    pub fn expires(&self) -> SystemTime {
+        self.expires
+    }
+
+    /// Returns whether the UTC time `time` lies past when the list expires, so that leap seconds
+    /// may have been announced for it since.
+    pub fn is_expired(&self, time: SystemTime) -> bool {
+        time >= self.expires
+    }
+
+    /// Returns how many seconds TAI runs ahead of UTC at the UTC time `time`.
+    ///
+    /// Before the first leap second in 1972, UTC's seconds were not SI seconds and the difference
+    /// was fractional; it is taken to be the table's first difference.
+    pub fn tai_offset(&self, time: SystemTime) -> i64 {
+        let secs = time.unix_nanos().div_euclid(1_000_000_000) as i64;
+        let later = self.changes.partition_point(|&(at, _)| at <= secs);
+        self.changes[later.saturating_sub(1)].1
+    }
+
+    /// Converts `time` read on the timescale `from` to the timescale `to`, saying how any leap
+    /// second was handled and whether the table had expired by then.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeError::Timestamp`] if the converted time cannot be represented.
+    pub fn convert(
+        &self,
+        time: SystemTime,
+        from: Timescale,
+        to: Timescale,
+    ) -> Result<Conversion, RangeError> {
+        if from == to {
+            return Ok(Conversion {
+                time,
+                leap: LeapHandling::None,
+                expired: false,
+            });
+        }
+        let (tai, into) = self.tai_from(time.as_nanoticks(), from);
+        let (nanoticks, out) = self.tai_to(tai, to);
+        let converted = SystemTime::from_nanoticks(nanoticks)?;
+        // only conversions to or from UTC read the table; the two sides differ by mere seconds
+        let utc = |scale| matches!(scale, Timescale::Utc | Timescale::UtcSmeared);
+        Ok(Conversion {
+            time: converted,
+            leap: match into {
+                LeapHandling::None | LeapHandling::Smeared if out != LeapHandling::None => out,
+                _ => into,
+            },
+            expired: (utc(from) || utc(to)) && self.is_expired(time.max(converted)),
+        })
+    }
+
+    /// Returns the current time on `timescale`, taking the system clock to count POSIX UTC.
+    ///
+    /// Once the table has expired, the result says so: a newer list is needed to be sure of it.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if [`SystemTime::now`] does.
+    pub fn now(&self, timescale: Timescale) -> Conversion {
+        match self.convert(SystemTime::now(), Timescale::Utc, timescale) {
+            Ok(now) => now,
+            Err(err) => panic!("current time unrepresentable on {timescale}: {err}"),
+        }
+    }
+
+    // the leap seconds, as the UTC nanotick at which each takes effect and the differences
+    // TAI − UTC in nanoticks before and after it
+    fn leaps(&self) -> impl Iterator<Item = (i128, i128, i128)> + '_ {
+        self.changes
+            .windows(2)
+            .map(|pair| (seconds(pair[1].0), seconds(pair[0].1), seconds(pair[1].1)))
+    }
+
+    // converts nanoticks on the timescale `from` to TAI
+    fn tai_from(&self, nanoticks: i128, from: Timescale) -> (i128, LeapHandling) {
+        let smeared = match from {
+            Timescale::Utc => false,
+            Timescale::UtcSmeared => true,
+            Timescale::Tai => return (nanoticks, LeapHandling::None),
+            Timescale::Gps => return (nanoticks + seconds(GPS_TAI_OFFSET), LeapHandling::None),
+        };
+        let mut offset = seconds(self.changes[0].1);
+        for (at, before, after) in self.leaps() {
+            if smeared {
+                let start = at - DAY / 2;
+                if nanoticks < start {
+                    break;
+                }
+                if nanoticks < start + DAY {
+                    let stretched = (nanoticks - start) * (DAY + after - before) / DAY;
+                    return (start + before + stretched, LeapHandling::Smeared);
+                }
+            } else {
+                // rounded down like the times from whole seconds it is compared with
+                let last_second = at + seconds(-1);
+                if nanoticks < last_second {
+                    break;
+                }
+                if nanoticks < at {
+                    let leap = match after > before {
+                        true => LeapHandling::Ambiguous,
+                        false => LeapHandling::Skipped,
+                    };
+                    return (nanoticks + before, leap);
+                }
+            }
+            offset = after;
+        }
+        (nanoticks + offset, LeapHandling::None)
+    }
+
+    // converts nanoticks on TAI to the timescale `to`
+    fn tai_to(&self, tai: i128, to: Timescale) -> (i128, LeapHandling) {
+        let smeared = match to {
+            Timescale::Utc => false,
+            Timescale::UtcSmeared => true,
+            Timescale::Tai => return (tai, LeapHandling::None),
+            Timescale::Gps => return (tai - seconds(GPS_TAI_OFFSET), LeapHandling::None),
+        };
+        let mut offset = seconds(self.changes[0].1);
+        for (at, before, after) in self.leaps() {
+            if smeared {
+                let start = at - DAY / 2;
+                let length = DAY + after - before;
+                if tai < start + before {
+                    break;
+                }
+                if tai < start + before + length {
+                    let squeezed = (tai - start - before) * DAY / length;
+                    return (start + squeezed, LeapHandling::Smeared);
+                }
+            } else {
+                if tai < at + before.min(after) {
+                    break;
+                }
+                if tai < at + after {
+                    return (tai - after, LeapHandling::Repeated);
+                }
+            }
+            offset = after;
+        }
+        (tai - offset, LeapHandling::None)
+    }
+}
+
+/// Converts an NTP timestamp to Unix seconds and the time they give, if it is representable.
+fn ntp_to_time(ntp: i64) -> Option<(i64, SystemTime)> {
+    let unix = ntp.checked_sub(NTP_UNIX_OFFSET)?;
+    Some((unix, SystemTime::from_unix_seconds(unix).ok()?))
+}
+
+/// Converts whole seconds to nanoticks, rounding down.
+fn seconds(secs: i64) -> i128 {
+    // 1 second = 625/54 ticks
+    (secs as i128 * 625 * NANOTICKS_PER_TICK as i128).div_euclid(54)
+}
+
+/// Returns the SHA-1 digest of `data`, as `leap-seconds.list` gives its hash.
+fn sha1(data: &[u8]) -> [u32; 5] {
+    let mut message = data.to_vec();
+    message.push(0x80);
+    message.resize((data.len() + 9).next_multiple_of(64) - 8, 0);
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+    let mut state: [u32; 5] = [
+        0x6745_2301,
+        0xefcd_ab89,
+        0x98ba_dcfe,
+        0x1032_5476,
+        0xc3d2_e1f0,
+    ];
+    for block in message.chunks(64) {
+        let mut words = [0; 80];
+        for (word, bytes) in words.iter_mut().zip(block.chunks(4)) {
+            *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.into_iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+                20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+                _ => (b ^ c ^ d, 0xca62_c1d6),
+            };
+            let t = a.rotate_left(5).wrapping_add(f).wrapping_add(e);
+            (e, d, c, b, a) = (
+                d,
+                c,
+                b.rotate_left(30),
+                a,
+                t.wrapping_add(k).wrapping_add(word),
+            );
+        }
+        for (state, word) in state.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(word);
+        }
+    }
+    state
+}
+
+/// An error reading a leap second list.
+#[derive(Debug)]
+pub enum LeapError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The numbered line is malformed, for the reason given.
+    Line(usize, &'static str),
+    /// The list lacks the part named.
+    Missing(&'static str),
+    /// The list does not match its hash.
+    Hash,
+}
+
+impl From<io::Error> for LeapError {
+    fn from(err: io::Error) -> LeapError {
+        LeapError::Io(err)
+    }
+}
+
+impl fmt::Display for LeapError {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LeapError::Io(err) => write!(fmter, "could not read leap second list: {err}"),
+            LeapError::Line(line, reason) => {
+                write!(fmter, "invalid leap second list at line {line}: {reason}")
+            }
+            LeapError::Missing(part) => write!(fmter, "leap second list lacks its {part}"),
+            LeapError::Hash => fmter.write_str("leap second list does not match its hash"),
+        }
+    }
+}
+
+impl std::error::Error for LeapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LeapError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2017-01-01 00:00:00 UTC in nanoseconds, just after the latest leap second
+    const NEW_YEAR: i128 = 1_483_228_800_000_000_000;
+
+    const SECOND: i128 = 1_000_000_000;
+
+    fn at(nanos: i128) -> SystemTime {
+        SystemTime::from_unix_nanos(nanos).unwrap()
+    }
+
+    // converts a Unix time in nanoseconds between timescales, returning it to the nanosecond
+    fn convert(
+        leaps: &LeapSeconds,
+        nanos: i128,
+        from: Timescale,
+        to: Timescale,
+    ) -> (i128, LeapHandling) {
+        let converted = leaps.convert(at(nanos), from, to).unwrap();
+        (converted.time.unix_nanos(), converted.leap)
+    }
+
+    #[test]
+    fn bundled_list() {
+        let leaps = LeapSeconds::bundled();
+        assert_eq!(leaps.changes.len(), 28);
+        assert_eq!(
+            leaps.updated().to_gregorian().to_string(),
+            "2026-01-06 11:14:18"
+        );
+        assert_eq!(
+            leaps.expires().to_gregorian().to_string(),
+            "2026-12-28 00:00:00"
+        );
+
+        assert_eq!(leaps.tai_offset(at(0)), 10);
+        assert_eq!(leaps.tai_offset(at(78_796_799 * SECOND)), 10);
+        assert_eq!(leaps.tai_offset(at(78_796_800 * SECOND)), 11);
+        assert_eq!(leaps.tai_offset(at(NEW_YEAR - 1)), 36);
+        assert_eq!(leaps.tai_offset(at(NEW_YEAR)), 37);
+        assert_eq!(leaps.tai_offset(SystemTime::MAX), 37);
+    }
+
+    #[test]
+    fn expiry() {
+        let leaps = LeapSeconds::bundled();
+        let expires = leaps.expires();
+        let before = expires - crate::DAY;
+        assert!(!leaps.is_expired(before));
+        assert!(leaps.is_expired(expires));
+
+        let (utc, smeared, tai, gps) = (
+            Timescale::Utc,
+            Timescale::UtcSmeared,
+            Timescale::Tai,
+            Timescale::Gps,
+        );
+        assert!(!leaps.convert(before, utc, tai).unwrap().expired);
+        assert!(leaps.convert(expires, utc, tai).unwrap().expired);
+        assert!(leaps.convert(expires, smeared, gps).unwrap().expired);
+        assert!(leaps.convert(expires, tai, utc).unwrap().expired);
+        assert!(
+            leaps
+                .convert(SystemTime::MAX, tai, smeared)
+                .unwrap()
+                .expired
+        );
+
+        // TAI and GPS time do not need the table, nor does staying on one scale
+        assert!(!leaps.convert(SystemTime::MAX, tai, gps).unwrap().expired);
+        assert!(!leaps.convert(SystemTime::MAX, utc, utc).unwrap().expired);
+    }
+
+    #[test]
+    fn sha1_digests() {
+        assert_eq!(
+            sha1(b""),
+            [0xda39a3ee, 0x5e6b4b0d, 0x3255bfef, 0x95601890, 0xafd80709]
+        );
+        assert_eq!(
+            sha1(b"abc"),
+            [0xa9993e36, 0x4706816a, 0xba3e2571, 0x7850c26c, 0x9cd0d89d]
+        );
+        // padding spills into a second block
+        let two_blocks = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        assert_eq!(
+            sha1(two_blocks),
+            [0x84983e44, 0x1c3bd26e, 0xbaae4aa1, 0xf95129e5, 0xe54670f1]
+        );
+    }
+
+    #[test]
+    fn malformed_lists() {
+        let list = "#$\t3960835200\n#@\t3991593600\n2272060800\t10\t# 1 Jan 1972\n\n";
+        assert!(LeapSeconds::parse(list).is_ok());
+
+        let errors = [
+            ("#@ 3991593600\n2272060800 10\n", "leap second list lacks its update time"),
+            ("#$ 3960835200\n2272060800 10\n", "leap second list lacks its expiry time"),
+            ("#$ 3960835200\n#@ 3991593600\n", "leap second list lacks its leap seconds"),
+            (
+                "#$ 3960835200\n#@ 3991593600\n2272060800 10\n2287785600 12\n",
+                "invalid leap second list at line 4: offset does not change by one second",
+            ),
+            (
+                "#$ 3960835200\n#@ 3991593600\n2287785600 11\n2272060800 10\n",
+                "invalid leap second list at line 4: leap seconds out of order",
+            ),
+            (
+                "#$ 3960835200\n#@ 3991593600\n2272060800 10 1972\n",
+                "invalid leap second list at line 3: expected an NTP timestamp and a TAI − UTC offset",
+            ),
+            ("#$ soon\n", "invalid leap second list at line 1: invalid update time"),
+            ("#h 49db2447 571e5e1b\n", "invalid leap second list at line 1: invalid hash"),
+            (
+                "#$ -9223372036854775808\n",
+                "invalid leap second list at line 1: invalid update time",
+            ),
+            (
+                "#$ 3960835200\n#@ 9223372036854775807\n",
+                "invalid leap second list at line 2: invalid expiry time",
+            ),
+            (
+                "#$ 3960835200\n#@ 3991593600\n-9223372036854775808 10\n",
+                "invalid leap second list at line 3: invalid NTP timestamp",
+            ),
+            (
+                "#$ 3960835200\n#@ 3991593600\n9223372036854775807 10\n",
+                "invalid leap second list at line 3: invalid NTP timestamp",
+            ),
+            (
+                "#$ 3960835200\n#@ 3991593600\n2272060800 -9223372036854775808\n\
+                 2287785600 9223372036854775807\n",
+                "invalid leap second list at line 4: offset does not change by one second",
+            ),
+        ];
+        for (list, message) in errors {
+            assert_eq!(LeapSeconds::parse(list).unwrap_err().to_string(), message);
+        }
+
+        // a list whose contents were changed after its hash was taken
+        let bundled = include_str!("../data/leap-seconds.list");
+        let tampered = bundled.replace("3692217600      37", "3692217601      37");
+        assert!(matches!(
+            LeapSeconds::parse(&tampered),
+            Err(LeapError::Hash)
+        ));
+    }
+
+    #[test]
+    fn inserted_leap_second() {
+        let leaps = LeapSeconds::bundled();
+        let (utc, tai, gps) = (Timescale::Utc, Timescale::Tai, Timescale::Gps);
+        let half = SECOND / 2;
+
+        // 2016-12-31 23:59:59.5 UTC, which POSIX clocks showed twice, is taken as the first
+        assert_eq!(
+            convert(leaps, NEW_YEAR - half, utc, tai),
+            (NEW_YEAR + 35 * SECOND + half, LeapHandling::Ambiguous)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR - 3 * half, utc, tai),
+            (NEW_YEAR + 34 * SECOND + half, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR + half, utc, tai),
+            (NEW_YEAR + 37 * SECOND + half, LeapHandling::None)
+        );
+
+        // and the second, 23:59:60.5, is shown as a repeat
+        assert_eq!(
+            convert(leaps, NEW_YEAR + 36 * SECOND + half, tai, utc),
+            (NEW_YEAR - half, LeapHandling::Repeated)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR + 35 * SECOND + half, tai, utc),
+            (NEW_YEAR - half, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR + 37 * SECOND, tai, utc),
+            (NEW_YEAR, LeapHandling::None)
+        );
+
+        assert_eq!(
+            convert(leaps, NEW_YEAR, utc, gps),
+            (NEW_YEAR + 18 * SECOND, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR + 18 * SECOND, gps, utc),
+            (NEW_YEAR, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR, gps, tai),
+            (NEW_YEAR + 19 * SECOND, LeapHandling::None)
+        );
+
+        // before the first leap second and long after the last
+        assert_eq!(
+            convert(leaps, 0, utc, tai),
+            (10 * SECOND, LeapHandling::None)
+        );
+        let later = 4_102_444_800 * SECOND;
+        assert_eq!(
+            convert(leaps, later, tai, utc),
+            (later - 37 * SECOND, LeapHandling::None)
+        );
+    }
+
+    #[test]
+    fn smeared_leap_second() {
+        let leaps = LeapSeconds::bundled();
+        let (smeared, utc, tai) = (Timescale::UtcSmeared, Timescale::Utc, Timescale::Tai);
+        let noon = 43_200 * SECOND;
+
+        // the leap second is spread over the day from noon to noon, half of it by midnight
+        assert_eq!(
+            convert(leaps, NEW_YEAR - noon - 1, smeared, tai),
+            (NEW_YEAR - noon + 36 * SECOND - 1, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR - noon, smeared, tai),
+            (NEW_YEAR - noon + 36 * SECOND, LeapHandling::Smeared)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR, smeared, tai),
+            (NEW_YEAR + 36 * SECOND + SECOND / 2, LeapHandling::Smeared)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR + noon, smeared, tai),
+            (NEW_YEAR + noon + 37 * SECOND, LeapHandling::None)
+        );
+
+        for offset in [-noon, -noon / 3, -1, 0, 1, noon / 7, noon - 1] {
+            let (there, _) = convert(leaps, NEW_YEAR + offset, smeared, tai);
+            assert_eq!(
+                convert(leaps, there, tai, smeared),
+                (NEW_YEAR + offset, LeapHandling::Smeared)
+            );
+        }
+
+        // POSIX clocks repeat the second that smeared clocks stretch
+        assert_eq!(
+            convert(leaps, NEW_YEAR, smeared, utc),
+            (NEW_YEAR - SECOND / 2, LeapHandling::Repeated)
+        );
+        assert_eq!(
+            convert(leaps, NEW_YEAR - noon / 2, smeared, utc),
+            (NEW_YEAR - noon / 2 + SECOND / 4, LeapHandling::Smeared)
+        );
+    }
+
+    #[test]
+    fn removed_leap_second() {
+        // a list in which the last second of 2016 was removed instead
+        let list = "#$ 3692217600\n#@ 3991593600\n2272060800 10\n3692217600 9\n";
+        let leaps = LeapSeconds::parse(list).unwrap();
+        let (utc, smeared, tai) = (Timescale::Utc, Timescale::UtcSmeared, Timescale::Tai);
+        let half = SECOND / 2;
+
+        assert_eq!(
+            convert(&leaps, NEW_YEAR - 3 * half, utc, tai),
+            (NEW_YEAR + 8 * SECOND + half, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(&leaps, NEW_YEAR - half, utc, tai),
+            (NEW_YEAR + 9 * SECOND + half, LeapHandling::Skipped)
+        );
+        assert_eq!(
+            convert(&leaps, NEW_YEAR, utc, tai),
+            (NEW_YEAR + 9 * SECOND, LeapHandling::None)
+        );
+
+        // no TAI time falls in the removed second
+        assert_eq!(
+            convert(&leaps, NEW_YEAR + 9 * SECOND - 1, tai, utc),
+            (NEW_YEAR - SECOND - 1, LeapHandling::None)
+        );
+        assert_eq!(
+            convert(&leaps, NEW_YEAR + 9 * SECOND, tai, utc),
+            (NEW_YEAR, LeapHandling::None)
+        );
+
+        assert_eq!(
+            convert(&leaps, NEW_YEAR, smeared, tai),
+            (NEW_YEAR + 9 * SECOND + half, LeapHandling::Smeared)
+        );
+    }
+}
@@ -0,0 +1,926 @@
+//! Time zones from the IANA time zone database, for reading C10 time on local wall clocks.
+//!
+//! Zones are read from compiled zoneinfo (TZif, RFC 8536) files such as those installed under
+//! [`ZONEINFO`], or from POSIX `TZ` rules such as `CET-1CEST,M3.5.0,M10.5.0/3`. Zoneinfo files
+//! list the zone's past transitions and end with such a rule for the times after them, so
+//! daylight saving time keeps being applied beyond the last listed transition.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), c10::zone::ZoneError> {
+//! let paris = c10::zone::TimeZone::named("Europe/Paris")?;
+//! let now = c10::SystemTime::now().in_zone(&paris);
+//! println!("{now}"); // e.g. "2023 20.06 58:33:33 CEST"
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::io;
+use std::path::{Component as PathComponent, Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::gregorian::DAYS_BEFORE_MONTH;
+use crate::{epochs, SystemTime};
+
+/// Where zoneinfo files are installed, unless the `TZDIR` environment variable says otherwise.
+pub const ZONEINFO: &str = "/usr/share/zoneinfo";
+
+/// The zone used when `TZ` is unset.
+const LOCALTIME: &str = "/etc/localtime";
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const SECONDS_PER_HOUR: i32 = 3_600;
+
+/// A kind of local time kept by a zone, such as Central European Summer Time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LocalType {
+    /// Seconds ahead of UTC (negative west of Greenwich).
+    pub offset: i32,
+    /// Whether this is daylight saving time.
+    pub dst: bool,
+    /// The abbreviation for this kind of time, such as `CEST`.
+    pub abbreviation: String,
+}
+
+impl LocalType {
+    fn utc() -> LocalType {
+        LocalType {
+            offset: 0,
+            dst: false,
+            abbreviation: "UTC".to_owned(),
+        }
+    }
+}
+
+/// A time zone: the offsets from UTC kept by a region over time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeZone {
+    name: String,
+    // Unix times in seconds at which the local time type changes, ascending
+    transitions: Vec<i64>,
+    // the index into `types` taking effect at each transition
+    kinds: Vec<u8>,
+    types: Vec<LocalType>,
+    // how local time is kept after the last transition
+    rule: Option<Rule>,
+}
+
+impl TimeZone {
+    /// Returns Coordinated Universal Time as a zone.
+    pub fn utc() -> TimeZone {
+        TimeZone {
+            name: "UTC".to_owned(),
+            transitions: Vec::new(),
+            kinds: Vec::new(),
+            types: vec![LocalType::utc()],
+            rule: None,
+        }
+    }
+
+    /// Returns the local time zone, as configured by the `TZ` environment variable or, when it is
+    /// unset, by `/etc/localtime`.
+    ///
+    /// `TZ` may name a zoneinfo file (optionally after a `:`), either within the zoneinfo
+    /// directory or by absolute path, or give a POSIX rule. An empty `TZ`, or a missing
+    /// `/etc/localtime`, means UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the zone cannot be read or understood.
+    pub fn local() -> Result<TimeZone, ZoneError> {
+        let tz = std::env::var("TZ").ok();
+        TimeZone::from_tz(tz.as_deref(), &zoneinfo_dir())
+    }
+
+    /// Returns [`TimeZone::local`] as read on first use, or UTC if it could not be read.
+    pub(crate) fn local_cached() -> &'static TimeZone {
+        static LOCAL: OnceLock<TimeZone> = OnceLock::new();
+        LOCAL.get_or_init(|| TimeZone::local().unwrap_or_else(|_| TimeZone::utc()))
+    }
+
+    /// Reads the zone called `name`, such as `Europe/Paris`, from the zoneinfo directory:
+    /// [`ZONEINFO`], or `TZDIR` if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` is not a path within the directory, or if its file cannot be
+    /// read or understood.
+    pub fn named(name: &str) -> Result<TimeZone, ZoneError> {
+        TimeZone::named_in(&zoneinfo_dir(), name)
+    }
+
+    /// Reads a zoneinfo file, naming the zone after the file's path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid zoneinfo file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<TimeZone, ZoneError> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)?;
+        TimeZone::from_tzif(&path.to_string_lossy(), &data)
+    }
+
+    /// Parses the contents of a zoneinfo file, giving the zone `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZoneError::Tzif`] if `data` is not a valid zoneinfo file, or
+    /// [`ZoneError::Rule`] if its closing rule is not valid.
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<TimeZone, ZoneError> {
+        let mut input = Input(data);
+        let mut header = Header::read(&mut input)?;
+        let mut time_size = 4;
+        if header.version >= 2 {
+            // skip the legacy 32-bit data for the 64-bit copy that follows
+            input.take(header.data_len(4))?;
+            header = Header::read(&mut input)?;
+            time_size = 8;
+        }
+        if header.leaps > 0 {
+            return Err(ZoneError::Tzif("leap second records are not supported"));
+        }
+        if header.types == 0 {
+            return Err(ZoneError::Tzif("no local time types"));
+        }
+
+        let transitions = input
+            .take(header.times * time_size)?
+            .chunks(time_size)
+            .map(|time| match *time {
+                [a, b, c, d] => i64::from(i32::from_be_bytes([a, b, c, d])),
+                _ => i64::from_be_bytes(time.try_into().unwrap_or_default()),
+            })
+            .collect::<Vec<_>>();
+        if transitions.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(ZoneError::Tzif("transitions out of order"));
+        }
+        let kinds = input.take(header.times)?.to_vec();
+        if kinds.iter().any(|&kind| usize::from(kind) >= header.types) {
+            return Err(ZoneError::Tzif(
+                "transition to an undefined local time type",
+            ));
+        }
+
+        let records = input.take(header.types * 6)?;
+        let chars = input.take(header.chars)?;
+        let types = records
+            .chunks(6)
+            .map(|record| {
+                let offset = i32::from_be_bytes([record[0], record[1], record[2], record[3]]);
+                let abbreviation = chars
+                    .get(usize::from(record[5])..)
+                    .and_then(|rest| rest.split(|&c| c == 0).next());
+                let abbreviation = match abbreviation {
+                    Some(abbreviation) if offset != i32::MIN => abbreviation,
+                    _ => return Err(ZoneError::Tzif("invalid local time type")),
+                };
+                Ok(LocalType {
+                    offset,
+                    dst: record[4] != 0,
+                    abbreviation: String::from_utf8_lossy(abbreviation).into_owned(),
+                })
+            })
+            .collect::<Result<Vec<_>, ZoneError>>()?;
+        input.take(header.std_flags + header.ut_flags)?;
+
+        let mut rule = None;
+        if header.version >= 2 {
+            let footer = match input.0 {
+                [b'\n', footer @ .., b'\n'] => footer,
+                _ => return Err(ZoneError::Tzif("missing closing rule")),
+            };
+            let footer = std::str::from_utf8(footer)
+                .map_err(|_| ZoneError::Tzif("closing rule is not text"))?;
+            if !footer.is_empty() {
+                rule = Some(Rule::parse(footer)?);
+            }
+        }
+
+        Ok(TimeZone {
+            name: name.to_owned(),
+            transitions,
+            kinds,
+            types,
+            rule,
+        })
+    }
+
+    /// Parses a POSIX `TZ` rule such as `EST5EDT,M3.2.0,M11.1.0`, naming the zone after it.
+    ///
+    /// When a daylight saving time is named without saying when it starts and ends, it is kept
+    /// from the second Sunday in March to the first Sunday in November, as in the United States.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZoneError::Rule`] if `rule` is not valid.
+    pub fn from_posix(rule: &str) -> Result<TimeZone, ZoneError> {
+        let rule = Rule::parse(rule)?;
+        Ok(TimeZone {
+            name: rule.text.clone(),
+            transitions: Vec::new(),
+            kinds: Vec::new(),
+            types: vec![rule.std.clone()],
+            rule: Some(rule),
+        })
+    }
+
+    /// Returns the zone's name, such as `Europe/Paris`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the kind of local time kept in the zone at `time`.
+    pub fn local_type(&self, time: SystemTime) -> &LocalType {
+        let secs = time.unix_nanos().div_euclid(1_000_000_000);
+        let secs = secs.clamp(i64::MIN.into(), i64::MAX.into()) as i64;
+        let after = self
+            .transitions
+            .partition_point(|&transition| transition <= secs);
+        match (&self.rule, after.checked_sub(1)) {
+            (Some(rule), _) if after == self.transitions.len() => rule.local_type(secs),
+            // times before the first transition keep the first local time type
+            (_, None) => &self.types[0],
+            (_, Some(last)) => &self.types[usize::from(self.kinds[last])],
+        }
+    }
+
+    // reads the zone called `name` within `dir`
+    fn named_in(dir: &Path, name: &str) -> Result<TimeZone, ZoneError> {
+        let relative = Path::new(name);
+        let within = relative
+            .components()
+            .all(|part| matches!(part, PathComponent::Normal(_)));
+        if name.is_empty() || !within {
+            return Err(ZoneError::Name(name.to_owned()));
+        }
+        let data = std::fs::read(dir.join(relative))?;
+        TimeZone::from_tzif(name, &data)
+    }
+
+    // finds the zone for the value of `TZ`, reading named zones from `dir`
+    fn from_tz(tz: Option<&str>, dir: &Path) -> Result<TimeZone, ZoneError> {
+        let tz = match tz {
+            Some(tz) => tz.strip_prefix(':').unwrap_or(tz),
+            None => {
+                return match std::fs::read(LOCALTIME) {
+                    Ok(data) => TimeZone::from_tzif(&localtime_name(), &data),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(TimeZone::utc()),
+                    Err(err) => Err(err.into()),
+                };
+            }
+        };
+        if tz.is_empty() {
+            return Ok(TimeZone::utc());
+        }
+        if tz.starts_with('/') {
+            return TimeZone::from_file(tz);
+        }
+        // a name that is not in the database may still be a rule
+        TimeZone::named_in(dir, tz).or_else(|err| match err {
+            ZoneError::Io(_) | ZoneError::Name(_) => TimeZone::from_posix(tz).map_err(|_| err),
+            err => Err(err),
+        })
+    }
+}
+
+/// Returns the zoneinfo directory: `TZDIR` if set, or else [`ZONEINFO`].
+pub(crate) fn zoneinfo_dir() -> PathBuf {
+    std::env::var_os("TZDIR").map_or_else(|| PathBuf::from(ZONEINFO), PathBuf::from)
+}
+
+/// Returns the name of the zone `/etc/localtime` links to, such as `Europe/Paris`, or
+/// `localtime` if it is not a link into a zoneinfo directory.
+fn localtime_name() -> String {
+    let target = std::fs::read_link(LOCALTIME).unwrap_or_default();
+    let target = target.to_string_lossy();
+    match target.split_once("zoneinfo/") {
+        Some((_, name)) => name.to_owned(),
+        None => "localtime".to_owned(),
+    }
+}
+
+/// Zoneinfo file contents yet to be read.
+struct Input<'a>(&'a [u8]);
+
+impl<'a> Input<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ZoneError> {
+        if len > self.0.len() {
+            return Err(ZoneError::Tzif("file is truncated"));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+}
+
+/// The header before each block of zoneinfo data, giving the number of each kind of record.
+struct Header {
+    version: u8,
+    ut_flags: usize,
+    std_flags: usize,
+    leaps: usize,
+    times: usize,
+    types: usize,
+    chars: usize,
+}
+
+impl Header {
+    fn read(input: &mut Input) -> Result<Header, ZoneError> {
+        if input.take(4)? != b"TZif" {
+            return Err(ZoneError::Tzif("not a zoneinfo file"));
+        }
+        let version = match input.take(1)?[0] {
+            0 => 1,
+            version @ b'2'..=b'9' => version - b'0',
+            _ => return Err(ZoneError::Tzif("unknown version")),
+        };
+        input.take(15)?;
+        let mut count = || -> Result<usize, ZoneError> {
+            let bytes = input.take(4)?;
+            Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+        };
+        Ok(Header {
+            version,
+            ut_flags: count()?,
+            std_flags: count()?,
+            leaps: count()?,
+            times: count()?,
+            types: count()?,
+            chars: count()?,
+        })
+    }
+
+    // the length of the data block following the header, with times of `time_size` bytes
+    fn data_len(&self, time_size: usize) -> usize {
+        self.times * (time_size + 1)
+            + self.types * 6
+            + self.chars
+            + self.leaps * (time_size + 4)
+            + self.std_flags
+            + self.ut_flags
+    }
+}
+
+/// A POSIX `TZ` rule: a standard time, and optionally a daylight saving time with the days it
+/// starts and ends each year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    text: String,
+    std: LocalType,
+    dst: Option<(LocalType, Change, Change)>,
+}
+
+/// When in the year daylight saving time starts or ends.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Change {
+    date: ChangeDate,
+    // seconds after local midnight, which may lie on another day
+    time: i32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ChangeDate {
+    /// `Jn`: day `n` of the year, 1–365, never counting February 29.
+    Julian(u16),
+    /// `n`: day `n` of the year, 0–365, counting February 29 in leap years.
+    Ordinal(u16),
+    /// `Mm.w.d`: day `d` of the week (0 is Sunday) in week `w` (5 is the last) of month `m`.
+    Weekday { month: u8, week: u8, weekday: u8 },
+}
+
+impl Rule {
+    fn parse(text: &str) -> Result<Rule, ZoneError> {
+        let invalid = || ZoneError::Rule(text.to_owned());
+        let mut cursor = RuleCursor(text.as_bytes());
+
+        let std = LocalType {
+            abbreviation: cursor.name().ok_or_else(invalid)?,
+            offset: -cursor.time(24).ok_or_else(invalid)?,
+            dst: false,
+        };
+        let mut dst = None;
+        if !cursor.0.is_empty() {
+            let abbreviation = cursor.name().ok_or_else(invalid)?;
+            let offset = match cursor.0.first() {
+                Some(b',') | None => std.offset + SECONDS_PER_HOUR,
+                Some(_) => -cursor.time(24).ok_or_else(invalid)?,
+            };
+            let (start, end) = match cursor.eat(b',') {
+                true => {
+                    let start = cursor.change().ok_or_else(invalid)?;
+                    cursor.eat(b',').then_some(()).ok_or_else(invalid)?;
+                    (start, cursor.change().ok_or_else(invalid)?)
+                }
+                false => (Change::weekday(3, 2), Change::weekday(11, 1)),
+            };
+            let dst_type = LocalType {
+                offset,
+                dst: true,
+                abbreviation,
+            };
+            dst = Some((dst_type, start, end));
+        }
+        if !cursor.0.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Rule {
+            text: text.to_owned(),
+            std,
+            dst,
+        })
+    }
+
+    fn local_type(&self, secs: i64) -> &LocalType {
+        let Some((dst, start, end)) = &self.dst else {
+            return &self.std;
+        };
+        // changes are given in the local time in force before them
+        let year = epochs::year_from_seconds(secs.saturating_add(self.std.offset.into()));
+        let start = start.local_seconds(year) - i64::from(self.std.offset);
+        let end = end.local_seconds(year) - i64::from(dst.offset);
+        let in_dst = match start <= end {
+            true => start <= secs && secs < end,
+            // in the southern hemisphere, daylight saving time spans the new year
+            false => !(end <= secs && secs < start),
+        };
+        match in_dst {
+            true => dst,
+            false => &self.std,
+        }
+    }
+}
+
+impl Change {
+    // the default time of 02:00 on day `weekday` in `week`, as used when no dates are given
+    fn weekday(month: u8, week: u8) -> Change {
+        Change {
+            date: ChangeDate::Weekday {
+                month,
+                week,
+                weekday: 0,
+            },
+            time: 2 * SECONDS_PER_HOUR,
+        }
+    }
+
+    // seconds from the epoch on the local wall clock to the change in `year`
+    fn local_seconds(&self, year: i64) -> i64 {
+        let leap = epochs::is_leap_year(year);
+        let day_of_year = match self.date {
+            ChangeDate::Julian(day) => i64::from(day) - 1 + i64::from(leap && day >= 60),
+            ChangeDate::Ordinal(day) => i64::from(day),
+            ChangeDate::Weekday {
+                month,
+                week,
+                weekday,
+            } => {
+                let (first, len) = month_days(usize::from(month), leap);
+                let first_weekday = (epochs::year_to_days(year) + first + 4).rem_euclid(7);
+                let mut day = (i64::from(weekday) - first_weekday).rem_euclid(7);
+                day += 7 * (i64::from(week) - 1);
+                while day >= len {
+                    day -= 7;
+                }
+                first + day
+            }
+        };
+        (epochs::year_to_days(year) + day_of_year) * SECONDS_PER_DAY + i64::from(self.time)
+    }
+}
+
+/// Returns the day of the year on which `month` (1–12) starts, counting from 0, and its length.
+fn month_days(month: usize, leap: bool) -> (i64, i64) {
+    let start = |month: usize| match month {
+        13 => 365 + i64::from(leap),
+        _ => i64::from(DAYS_BEFORE_MONTH[month - 1]) + i64::from(leap && month > 2),
+    };
+    (start(month), start(month + 1) - start(month))
+}
+
+/// A POSIX `TZ` rule yet to be parsed.
+struct RuleCursor<'a>(&'a [u8]);
+
+impl RuleCursor<'_> {
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.0.first() == Some(&byte);
+        if found {
+            self.0 = &self.0[1..];
+        }
+        found
+    }
+
+    // an abbreviation: three or more letters, or `<...>` quoting letters, digits and signs
+    fn name(&mut self) -> Option<String> {
+        let (name, rest) = match self.eat(b'<') {
+            true => {
+                let end = self.0.iter().position(|&c| c == b'>')?;
+                let name = &self.0[..end];
+                let valid = |c: &u8| c.is_ascii_alphanumeric() || *c == b'+' || *c == b'-';
+                name.iter()
+                    .all(valid)
+                    .then_some((name, &self.0[end + 1..]))?
+            }
+            false => {
+                let end = self.0.iter().position(|c| !c.is_ascii_alphabetic());
+                self.0.split_at(end.unwrap_or(self.0.len()))
+            }
+        };
+        self.0 = rest;
+        (name.len() >= 3).then(|| String::from_utf8_lossy(name).into_owned())
+    }
+
+    // a number of up to `digits` digits
+    fn number(&mut self, digits: usize) -> Option<u32> {
+        let len = self
+            .0
+            .iter()
+            .take(digits)
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        let (number, rest) = self.0.split_at(len);
+        self.0 = rest;
+        std::str::from_utf8(number).ok()?.parse().ok()
+    }
+
+    // `[+-]hh[:mm[:ss]]` in seconds, with hours up to `max_hours`
+    fn time(&mut self, max_hours: u32) -> Option<i32> {
+        let sign = match self.eat(b'-') {
+            true => -1,
+            false => {
+                self.eat(b'+');
+                1
+            }
+        };
+        let hours = self.number(3).filter(|&hours| hours <= max_hours)?;
+        let mut secs = hours * 3_600;
+        for scale in [60, 1] {
+            if !self.eat(b':') {
+                break;
+            }
+            secs += self.number(2).filter(|&part| part < 60)? * scale;
+        }
+        Some(sign * secs as i32)
+    }
+
+    // a date, optionally followed by `/time`
+    fn change(&mut self) -> Option<Change> {
+        let date = if self.eat(b'J') {
+            ChangeDate::Julian(self.number(3).filter(|day| (1..=365).contains(day))? as u16)
+        } else if self.eat(b'M') {
+            let month = self.number(2).filter(|month| (1..=12).contains(month))?;
+            self.eat(b'.').then_some(())?;
+            let week = self.number(1).filter(|week| (1..=5).contains(week))?;
+            self.eat(b'.').then_some(())?;
+            let weekday = self.number(1).filter(|&weekday| weekday <= 6)?;
+            ChangeDate::Weekday {
+                month: month as u8,
+                week: week as u8,
+                weekday: weekday as u8,
+            }
+        } else {
+            ChangeDate::Ordinal(self.number(3).filter(|&day| day <= 365)? as u16)
+        };
+        let time = match self.eat(b'/') {
+            true => self.time(167)?,
+            false => 2 * SECONDS_PER_HOUR,
+        };
+        Some(Change { date, time })
+    }
+}
+
+/// An instant together with the local time kept by a zone at that instant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZonedTime {
+    time: SystemTime,
+    local: LocalType,
+}
+
+impl SystemTime {
+    /// Returns this instant as seen in `zone`.
+    pub fn in_zone(&self, zone: &TimeZone) -> ZonedTime {
+        ZonedTime::new(*self, zone)
+    }
+}
+
+impl ZonedTime {
+    /// Returns `time` as seen in `zone`.
+    pub fn new(time: SystemTime, zone: &TimeZone) -> ZonedTime {
+        ZonedTime {
+            time,
+            local: zone.local_type(time).clone(),
+        }
+    }
+
+    /// Returns the current time as seen in `zone`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SystemTime::now`] does.
+    pub fn now(zone: &TimeZone) -> ZonedTime {
+        ZonedTime::new(SystemTime::now(), zone)
+    }
+
+    /// Returns the instant itself.
+    pub fn time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Returns the kind of local time kept at this instant.
+    pub fn local_type(&self) -> &LocalType {
+        &self.local
+    }
+
+    /// Returns the offset of local time from UTC in seconds.
+    pub fn offset(&self) -> i32 {
+        self.local.offset
+    }
+
+    /// Returns whether daylight saving time is in effect.
+    pub fn is_dst(&self) -> bool {
+        self.local.dst
+    }
+
+    /// Returns the instant as read on the local wall clock, that is, shifted by its offset.
+    pub fn wall_clock(&self) -> SystemTime {
+        self.time
+            .shifted_nanoticks(crate::seconds_to_nanoticks(self.local.offset.into()))
+    }
+
+    /// Returns the year, decaday, and day components of the local date.
+    pub fn date_components(&self) -> (i64, u64, u64) {
+        self.wall_clock().date_components()
+    }
+
+    /// Returns the interval, centival, and tick components of the local time of day.
+    pub fn time_components(&self) -> (u64, u64, u64) {
+        self.wall_clock().time_components()
+    }
+}
+
+/// Formats as `YYYY DD.dd II:CC:TT ABBR`, with sub-tick digits following the time when a
+/// precision is given.
+impl fmt::Display for ZonedTime {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.wall_clock(), fmter)?;
+        write!(fmter, " {}", self.local.abbreviation)
+    }
+}
+
+/// An error returned when a time zone cannot be found or understood.
+#[derive(Debug)]
+pub enum ZoneError {
+    /// The zoneinfo file could not be read.
+    Io(io::Error),
+    /// The zone name does not lie within the zoneinfo directory.
+    Name(String),
+    /// The zoneinfo file is malformed, for the reason given.
+    Tzif(&'static str),
+    /// The POSIX `TZ` rule is malformed.
+    Rule(String),
+}
+
+impl From<io::Error> for ZoneError {
+    fn from(err: io::Error) -> ZoneError {
+        ZoneError::Io(err)
+    }
+}
+
+impl fmt::Display for ZoneError {
+    fn fmt(&self, fmter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ZoneError::Io(err) => write!(fmter, "could not read zoneinfo: {err}"),
+            ZoneError::Name(name) => write!(fmter, "invalid time zone name {name:?}"),
+            ZoneError::Tzif(reason) => write!(fmter, "invalid zoneinfo file: {reason}"),
+            ZoneError::Rule(rule) => write!(fmter, "invalid TZ rule {rule:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ZoneError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ZoneError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gregorian::utc;
+
+    // zoneinfo files from the 2025b release of the tz database
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/zoneinfo")
+    }
+
+    fn fixture(name: &str) -> TimeZone {
+        TimeZone::named_in(&fixtures(), name).unwrap()
+    }
+
+    // the offset and abbreviation in `zone` at `time`
+    fn kept(zone: &TimeZone, time: SystemTime) -> (i32, &str) {
+        let local = zone.local_type(time);
+        (local.offset, &local.abbreviation)
+    }
+
+    // a zoneinfo file without transitions, relying on its closing rule as slim files do
+    fn slim(rule: &str) -> Vec<u8> {
+        let mut block = b"TZif2".to_vec();
+        block.extend([0; 15]);
+        for count in [0_u32, 0, 0, 0, 1, 4] {
+            block.extend(count.to_be_bytes());
+        }
+        block.extend([0, 0, 0, 0, 0, 0]);
+        block.extend(b"UTC\0");
+        // the legacy block and the 64-bit one are the same without any transitions
+        let mut data = block.repeat(2);
+        data.extend(format!("\n{rule}\n").bytes());
+        data
+    }
+
+    #[test]
+    fn europe() {
+        let paris = fixture("Europe/Paris");
+        assert_eq!(paris.name(), "Europe/Paris");
+        assert_eq!(kept(&paris, utc(2023, 1, 15, 12, 0, 0)), (3_600, "CET"));
+        assert_eq!(kept(&paris, utc(2023, 7, 15, 12, 0, 0)), (7_200, "CEST"));
+
+        // summer time starts at 01:00 UTC on the last Sunday in March and ends in October
+        assert_eq!(kept(&paris, utc(2023, 3, 26, 0, 59, 59)).1, "CET");
+        assert_eq!(kept(&paris, utc(2023, 3, 26, 1, 0, 0)).1, "CEST");
+        assert_eq!(kept(&paris, utc(2023, 10, 29, 0, 59, 59)).1, "CEST");
+        assert_eq!(kept(&paris, utc(2023, 10, 29, 1, 0, 0)).1, "CET");
+
+        // before standard time, and after the last transition listed, which follows the rule
+        assert_eq!(kept(&paris, utc(1880, 1, 1, 0, 0, 0)), (561, "LMT"));
+        assert_eq!(kept(&paris, utc(2100, 3, 28, 0, 59, 59)).1, "CET");
+        assert_eq!(kept(&paris, utc(2100, 3, 28, 1, 0, 0)).1, "CEST");
+    }
+
+    #[test]
+    fn americas() {
+        let new_york = fixture("America/New_York");
+        assert_eq!(
+            kept(&new_york, utc(2023, 3, 12, 6, 59, 59)),
+            (-18_000, "EST")
+        );
+        assert_eq!(kept(&new_york, utc(2023, 3, 12, 7, 0, 0)), (-14_400, "EDT"));
+        assert_eq!(kept(&new_york, utc(2023, 11, 5, 5, 59, 59)).1, "EDT");
+        assert_eq!(kept(&new_york, utc(2023, 11, 5, 6, 0, 0)).1, "EST");
+        assert_eq!(kept(&new_york, utc(1943, 6, 1, 0, 0, 0)).1, "EWT");
+        assert_eq!(kept(&new_york, utc(2100, 3, 14, 7, 0, 0)).1, "EDT");
+        assert_eq!(kept(&new_york, utc(2100, 3, 14, 6, 59, 59)).1, "EST");
+    }
+
+    #[test]
+    fn southern_and_fractional() {
+        // half an hour of daylight saving over the southern summer
+        let lord_howe = fixture("Australia/Lord_Howe");
+        assert_eq!(kept(&lord_howe, utc(2023, 1, 15, 0, 0, 0)), (39_600, "+11"));
+        assert_eq!(
+            kept(&lord_howe, utc(2023, 7, 15, 0, 0, 0)),
+            (37_800, "+1030")
+        );
+        assert_eq!(kept(&lord_howe, utc(2101, 1, 15, 0, 0, 0)).1, "+11");
+        assert_eq!(kept(&lord_howe, utc(2101, 7, 15, 0, 0, 0)).1, "+1030");
+        // 02:00 local standard time on the first Sunday in October 2100 (the 3rd)
+        assert_eq!(kept(&lord_howe, utc(2100, 10, 2, 15, 29, 59)).1, "+1030");
+        assert_eq!(kept(&lord_howe, utc(2100, 10, 2, 15, 30, 0)).1, "+11");
+
+        let kolkata = fixture("Asia/Kolkata");
+        assert_eq!(kept(&kolkata, utc(2023, 7, 15, 0, 0, 0)), (19_800, "IST"));
+        assert_eq!(kept(&kolkata, utc(2300, 1, 1, 0, 0, 0)), (19_800, "IST"));
+
+        // Samoa skipped 30 December 2011 to cross the date line
+        let apia = fixture("Pacific/Apia");
+        assert_eq!(kept(&apia, utc(2011, 12, 30, 9, 59, 59)), (-36_000, "-10"));
+        assert_eq!(kept(&apia, utc(2011, 12, 30, 10, 0, 0)), (50_400, "+14"));
+        assert_eq!(kept(&apia, utc(2050, 1, 1, 0, 0, 0)), (46_800, "+13"));
+    }
+
+    #[test]
+    fn zoned_components() {
+        let paris = fixture("Europe/Paris");
+        let summer = utc(2023, 7, 15, 12, 0, 0).in_zone(&paris);
+        assert_eq!(summer.time(), utc(2023, 7, 15, 12, 0, 0));
+        assert_eq!(summer.offset(), 7_200);
+        assert!(summer.is_dst());
+        // 14:00 is 58⅓ intervals into the day
+        assert_eq!(summer.date_components(), (2023, 20, 6));
+        assert_eq!(summer.time_components(), (58, 33, 33));
+        assert_eq!(summer.to_string(), "2023 20.06 58:33:33 CEST");
+        assert_eq!(format!("{summer:.2}"), "2023 20.06 58:33:33.33 CEST");
+
+        // local dates can differ from UTC ones
+        let new_year = utc(2023, 12, 31, 23, 30, 0).in_zone(&paris);
+        assert_eq!(new_year.date_components(), (2024, 1, 1));
+        let utc_zone = utc(2023, 12, 31, 23, 30, 0).in_zone(&TimeZone::utc());
+        assert_eq!(utc_zone.date_components(), (2023, 37, 5));
+        assert_eq!(utc_zone.local_type().abbreviation, "UTC");
+    }
+
+    #[test]
+    fn posix_rules() {
+        let paris = TimeZone::from_posix("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(paris.name(), "CET-1CEST,M3.5.0,M10.5.0/3");
+        for time in [
+            utc(1999, 3, 28, 1, 0, 0),
+            utc(2023, 6, 1, 0, 0, 0),
+            utc(2023, 10, 29, 0, 59, 59),
+            utc(2023, 10, 29, 1, 0, 0),
+            utc(2100, 3, 28, 1, 0, 0),
+        ] {
+            assert_eq!(
+                paris.local_type(time),
+                fixture("Europe/Paris").local_type(time)
+            );
+        }
+
+        // the United States' rules are assumed when none are given
+        let eastern = TimeZone::from_posix("EST5EDT").unwrap();
+        assert_eq!(kept(&eastern, utc(2024, 3, 10, 7, 0, 0)), (-14_400, "EDT"));
+        assert_eq!(
+            kept(&eastern, utc(2024, 3, 10, 6, 59, 59)),
+            (-18_000, "EST")
+        );
+
+        let kolkata = TimeZone::from_posix("<+0530>-5:30").unwrap();
+        assert_eq!(kept(&kolkata, utc(2023, 1, 1, 0, 0, 0)), (19_800, "+0530"));
+
+        // daylight saving time all year, and days counted with and without February 29
+        let always = TimeZone::from_posix("EST5EDT,0/0,J365/25").unwrap();
+        assert!(always.local_type(utc(2024, 1, 1, 5, 0, 0)).dst);
+        assert!(always.local_type(utc(2024, 12, 31, 23, 0, 0)).dst);
+        let julian = TimeZone::from_posix("AAA0BBB,J60/0,J61/0").unwrap();
+        assert!(julian.local_type(utc(2024, 3, 1, 12, 0, 0)).dst);
+        let ordinal = TimeZone::from_posix("AAA0BBB,59/0,60/0").unwrap();
+        assert!(ordinal.local_type(utc(2024, 2, 29, 12, 0, 0)).dst);
+        assert!(!ordinal.local_type(utc(2024, 3, 1, 12, 0, 0)).dst);
+
+        for invalid in [
+            "",
+            "C1",
+            "CET",
+            "CET-1CEST,M13.1.0,M10.5.0",
+            "CET-1CEST,M3.5.0",
+            "CET-1CEST,M3.5.0,M10.5.0/3x",
+            "<+05>",
+            "EST25",
+        ] {
+            assert!(TimeZone::from_posix(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn slim_files() {
+        let zone = TimeZone::from_tzif("slim", &slim("CET-1CEST,M3.5.0,M10.5.0/3")).unwrap();
+        assert_eq!(kept(&zone, utc(2023, 7, 15, 12, 0, 0)), (7_200, "CEST"));
+        assert_eq!(kept(&zone, utc(1990, 1, 1, 0, 0, 0)), (3_600, "CET"));
+
+        // without a rule, the only local time type holds
+        let zone = TimeZone::from_tzif("empty", &slim("")).unwrap();
+        assert_eq!(kept(&zone, utc(2023, 7, 15, 12, 0, 0)), (0, "UTC"));
+    }
+
+    #[test]
+    fn malformed_files() {
+        let paris = std::fs::read(fixtures().join("Europe/Paris")).unwrap();
+        for len in [0, 4, 40, 44, 100, 1_000, paris.len() - 1] {
+            assert!(TimeZone::from_tzif("cut", &paris[..len]).is_err(), "{len}");
+        }
+        let mut bad = paris.clone();
+        bad[0] = b'X';
+        assert!(matches!(
+            TimeZone::from_tzif("bad", &bad),
+            Err(ZoneError::Tzif(_))
+        ));
+        assert!(matches!(
+            TimeZone::from_tzif("bad rule", &slim("CET-1CEST,M3")),
+            Err(ZoneError::Rule(_))
+        ));
+    }
+
+    #[test]
+    fn tz_variable() {
+        let dir = fixtures();
+        let from_tz = |tz| TimeZone::from_tz(Some(tz), &dir);
+        assert_eq!(from_tz("").unwrap(), TimeZone::utc());
+        assert_eq!(from_tz(":Europe/Paris").unwrap(), fixture("Europe/Paris"));
+        assert_eq!(from_tz("Etc/UTC").unwrap().name(), "Etc/UTC");
+
+        let path = dir.join("Asia/Kolkata");
+        let by_path = from_tz(path.to_str().unwrap()).unwrap();
+        assert_eq!(kept(&by_path, utc(2023, 1, 1, 0, 0, 0)), (19_800, "IST"));
+
+        let rule = from_tz("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        assert_eq!(rule.name(), "EST5EDT,M3.2.0,M11.1.0");
+
+        assert!(matches!(from_tz("Europe/Nowhere"), Err(ZoneError::Io(_))));
+        assert!(matches!(
+            from_tz("../zoneinfo/Europe/Paris"),
+            Err(ZoneError::Name(_))
+        ));
+    }
+}